@@ -83,5 +83,56 @@ pub enum SharedError {
      SplConversionNotAllowed,
 
      #[msg("This deployment requires the creator to co-sign")]
-     InvalidCreatorCosigner
+     InvalidCreatorCosigner,
+
+     #[msg("Edition was not printed from the supplied master edition")]
+     EditionParentMismatch,
+
+     #[msg("Mint does not have a close authority")]
+     MissingMintCloseAuthority,
+
+     #[msg("Bad mint close authority")]
+     BadMintCloseAuthority,
+
+     #[msg("Missing metadata account")]
+     MissingMetadata,
+
+     #[msg("Missing collection metadata account")]
+     MissingCollectionMetadata,
+
+     #[msg("Missing or invalid group member account")]
+     MissingGroupMember,
+
+     #[msg("Asset does not belong to the expected collection")]
+     NotInExpectedCollection,
+
+     #[msg("Bad metadata update authority")]
+     BadMetadataUpdateAuthority,
+
+     #[msg("Mint is non-transferable")]
+     NonTransferableMint,
+
+     #[msg("Bad permanent delegate")]
+     BadPermanentDelegate,
+
+     #[msg("All editions of this master edition have been printed")]
+     EditionSupplyExhausted,
+
+     #[msg("Bad edition marker")]
+     BadEditionMarker,
+
+     #[msg("Signer is not in the creators list of the metadata")]
+     CreatorNotFound,
+
+     #[msg("Minting would exceed the max supply")]
+     MaxSupplyExceeded,
+
+     #[msg("Mint decimals do not match the token standard")]
+     BadMintDecimals,
+
+     #[msg("Bad royalty field in token metadata")]
+     BadRoyaltyField,
+
+     #[msg("Missing authorization rules program")]
+     MissingAuthorizationRulesProgram
 }
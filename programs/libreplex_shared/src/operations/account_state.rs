@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use solana_program::program::{invoke, invoke_signed};
+use spl_token_2022::{
+    extension::StateWithExtensions,
+    instruction::{freeze_account, thaw_account},
+    state::{Account, AccountState},
+};
+
+/// Returns true if the token account is frozen. Works for both token programs.
+pub fn is_token_account_frozen(token_account: &AccountInfo) -> Result<bool> {
+    let token_account_data = token_account.try_borrow_data()?;
+    let token_account_obj = StateWithExtensions::<Account>::unpack(&token_account_data)?;
+    Ok(token_account_obj.base.state == AccountState::Frozen)
+}
+
+/// Thaws a holder's token account using the freeze authority of the mint.
+///
+/// Any gating (KYC etc) is up to the caller and must happen before this is
+/// called.
+pub fn thaw_token_account<'info>(
+    token_program: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    freeze_authority: &AccountInfo<'info>,
+    authority_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    let ix = thaw_account(
+        token_program.key,
+        token_account.key,
+        mint.key,
+        freeze_authority.key,
+        &[],
+    )?;
+    let account_infos = &[
+        token_account.to_account_info(),
+        mint.to_account_info(),
+        freeze_authority.to_account_info(),
+    ];
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, account_infos, x)?;
+        }
+        None => {
+            invoke(&ix, account_infos)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Freezes a holder's token account using the freeze authority of the mint.
+pub fn freeze_token_account<'info>(
+    token_program: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    freeze_authority: &AccountInfo<'info>,
+    authority_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    let ix = freeze_account(
+        token_program.key,
+        token_account.key,
+        mint.key,
+        freeze_authority.key,
+        &[],
+    )?;
+    let account_infos = &[
+        token_account.to_account_info(),
+        mint.to_account_info(),
+        freeze_authority.to_account_info(),
+    ];
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, account_infos, x)?;
+        }
+        None => {
+            invoke(&ix, account_infos)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use mpl_token_metadata::{
+    accounts::{Edition, MasterEdition, Metadata},
+    types::{Collection, Key as MetadataKey, TokenStandard},
+};
+use spl_token_2022::{
+    extension::{
+        metadata_pointer::MetadataPointer, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    state::Mint,
+};
+use spl_token_group_interface::state::{TokenGroup, TokenGroupMember};
+
+use crate::{operations::read_token_group_member, SharedError};
+
+/// Where the metadata of an asset lives.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetadataSource {
+    None,
+    /// Metaplex metadata PDA of the mint.
+    Metaplex,
+    /// Token-2022 metadata stored on the mint itself.
+    Token2022Embedded,
+    /// Token-2022 metadata pointer to a separate account.
+    External(Pubkey),
+}
+
+/// Metaplex edition of an asset.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EditionInfo {
+    MasterEdition { supply: u64, max_supply: Option<u64> },
+    Edition { parent: Pubkey, edition: u64 },
+}
+
+/// Everything this crate knows about a mint, for routing and policy checks.
+#[derive(Clone, Debug)]
+pub struct AssetInfo {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub decimals: u8,
+    pub supply: u64,
+    pub metadata_source: MetadataSource,
+    /// Only known for Metaplex metadata.
+    pub token_standard: Option<TokenStandard>,
+    pub edition: Option<EditionInfo>,
+    /// Metaplex collection, verified or not.
+    pub collection: Option<Collection>,
+    /// Token-2022 group, if the mint itself is a group.
+    pub group: Option<TokenGroup>,
+    /// Token-2022 group membership, stored on the mint or in the supplied
+    /// group member account.
+    pub group_member: Option<TokenGroupMember>,
+    /// Mint extensions. Always empty for legacy mints.
+    pub extensions: Vec<ExtensionType>,
+}
+
+impl AssetInfo {
+    /// Loads the asset info of a mint.
+    ///
+    /// metadata is the Metaplex metadata PDA and edition the Metaplex
+    /// (master) edition PDA of the mint. Both are optional and are ignored if
+    /// they are empty.
+    ///
+    /// group_member is only read if the mint has a GroupMemberPointer but no
+    /// embedded membership, see read_token_group_member.
+    pub fn load<'info>(
+        mint: &AccountInfo<'info>,
+        metadata: Option<&AccountInfo<'info>>,
+        edition: Option<&AccountInfo<'info>>,
+        group_member: Option<&AccountInfo<'info>>,
+        token_group_program_id: Option<Pubkey>,
+    ) -> Result<AssetInfo> {
+        let token_program = *mint.owner;
+        if token_program != spl_token::ID && token_program != spl_token_2022::ID {
+            return Err(SharedError::UnexpectedTokenType.into());
+        }
+
+        let mut asset_info = {
+            let mint_data = mint.try_borrow_data()?;
+            let mint_obj = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+
+            let metadata_source = match mint_obj.get_extension::<MetadataPointer>() {
+                Ok(x) => match Option::<Pubkey>::from(x.metadata_address) {
+                    Some(address) if address == mint.key() => MetadataSource::Token2022Embedded,
+                    Some(address) => MetadataSource::External(address),
+                    None => MetadataSource::None,
+                },
+                Err(_) => MetadataSource::None,
+            };
+
+            AssetInfo {
+                mint: mint.key(),
+                token_program,
+                decimals: mint_obj.base.decimals,
+                supply: mint_obj.base.supply,
+                metadata_source,
+                token_standard: None,
+                edition: None,
+                collection: None,
+                group: mint_obj.get_extension::<TokenGroup>().ok().copied(),
+                group_member: mint_obj.get_extension::<TokenGroupMember>().ok().copied(),
+                extensions: mint_obj.get_extension_types()?,
+            }
+        };
+
+        if asset_info.group_member.is_none()
+            && group_member.is_some()
+            && asset_info.has_extension(ExtensionType::GroupMemberPointer)
+        {
+            asset_info.group_member = Some(read_token_group_member(
+                mint,
+                group_member,
+                token_group_program_id,
+            )?);
+        }
+
+        if let Some(x) = metadata.filter(|x| !x.data_is_empty()) {
+            if x.key() != Metadata::find_pda(&mint.key()).0 {
+                return Err(SharedError::DerivedKeyInvalid.into());
+            }
+            let metadata_obj = Metadata::try_from(x)?;
+            asset_info.metadata_source = MetadataSource::Metaplex;
+            asset_info.token_standard = metadata_obj.token_standard;
+            asset_info.collection = metadata_obj.collection;
+        }
+
+        if let Some(x) = edition.filter(|x| !x.data_is_empty()) {
+            if x.key() != MasterEdition::find_pda(&mint.key()).0 {
+                return Err(SharedError::DerivedKeyInvalid.into());
+            }
+            let key = x.try_borrow_data()?[0];
+            asset_info.edition = if key == MetadataKey::MasterEditionV2 as u8 {
+                let master_edition_obj = MasterEdition::try_from(x)?;
+                Some(EditionInfo::MasterEdition {
+                    supply: master_edition_obj.supply,
+                    max_supply: master_edition_obj.max_supply,
+                })
+            } else if key == MetadataKey::EditionV1 as u8 {
+                let edition_obj = Edition::try_from(x)?;
+                Some(EditionInfo::Edition {
+                    parent: edition_obj.parent,
+                    edition: edition_obj.edition,
+                })
+            } else {
+                None
+            };
+        }
+
+        Ok(asset_info)
+    }
+
+    pub fn is_token_2022(&self) -> bool {
+        self.token_program == spl_token_2022::ID
+    }
+
+    pub fn has_extension(&self, extension_type: ExtensionType) -> bool {
+        self.extensions.contains(&extension_type)
+    }
+}
@@ -0,0 +1,175 @@
+use mpl_token_metadata::{
+    accounts::{Edition, EditionMarker, EditionMarkerV2, Metadata},
+    instructions::BurnV1Builder,
+    types::TokenStandard,
+    EDITION_MARKER_BIT_SIZE,
+};
+
+use anchor_lang::prelude::*;
+use solana_program::program::{invoke, invoke_signed};
+
+use crate::SharedError;
+
+/// Accounts of the master edition that a print edition was printed from.
+pub struct MasterEditionAccounts<'a, 'info> {
+    pub master_edition: &'a AccountInfo<'info>,
+    pub master_edition_mint: &'a AccountInfo<'info>,
+    pub master_edition_token: &'a AccountInfo<'info>,
+    pub edition_marker: &'a AccountInfo<'info>,
+}
+
+/// Returns the edition marker PDA that tracks the given edition number.
+///
+/// NonFungibleEditions use one marker per 248 editions, whereas
+/// ProgrammableNonFungibleEditions share a single V2 marker per master mint.
+pub fn get_edition_marker_address(
+    master_edition_mint: &Pubkey,
+    edition_number: u64,
+    token_standard: &TokenStandard,
+) -> Result<Pubkey> {
+    match token_standard {
+        TokenStandard::NonFungibleEdition => Ok(EditionMarker::find_pda(
+            master_edition_mint,
+            &(edition_number / EDITION_MARKER_BIT_SIZE).to_string(),
+        )
+        .0),
+        TokenStandard::ProgrammableNonFungibleEdition => {
+            Ok(EditionMarkerV2::find_pda(master_edition_mint).0)
+        }
+        _ => Err(SharedError::UnexpectedTokenType.into()),
+    }
+}
+
+pub fn burn_edition<'info>(
+    token_program: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    owner_wallet: &AccountInfo<'info>,
+    edition: &AccountInfo<'info>,
+    token_record: Option<&AccountInfo<'info>>,
+    mint: &AccountInfo<'info>,
+    metadata: &AccountInfo<'info>,
+    master_accounts: &MasterEditionAccounts<'_, 'info>,
+    system_program: &AccountInfo<'info>,
+    sysvar_instructions: &AccountInfo<'info>,
+    authority_seeds: Option<&[&[&[u8]]]>,
+    collection_metadata: Option<&AccountInfo<'info>>,
+) -> Result<()> {
+    let metadata_obj = Metadata::try_from(metadata)?;
+    let token_standard = metadata_obj
+        .token_standard
+        .ok_or(SharedError::UnexpectedTokenType)?;
+
+    let edition_obj = Edition::try_from(edition)?;
+    if edition_obj.parent != master_accounts.master_edition.key() {
+        return Err(SharedError::EditionParentMismatch.into());
+    }
+
+    let expected_edition_marker = get_edition_marker_address(
+        &master_accounts.master_edition_mint.key(),
+        edition_obj.edition,
+        &token_standard,
+    )?;
+    if expected_edition_marker != master_accounts.edition_marker.key() {
+        return Err(SharedError::DerivedKeyInvalid.into());
+    }
+
+    if token_standard == TokenStandard::ProgrammableNonFungibleEdition && token_record.is_none() {
+        return Err(SharedError::MissingTokenRecord.into());
+    }
+
+    let mut burn_builder = BurnV1Builder::new();
+
+    burn_builder
+        .authority(owner_wallet.key())
+        .collection_metadata(collection_metadata.map(|x| x.key()))
+        .metadata(metadata.key())
+        .edition(Some(edition.key()))
+        .mint(mint.key())
+        .token(token_account.key())
+        .master_edition(Some(master_accounts.master_edition.key()))
+        .master_edition_mint(Some(master_accounts.master_edition_mint.key()))
+        .master_edition_token(Some(master_accounts.master_edition_token.key()))
+        .edition_marker(Some(master_accounts.edition_marker.key()))
+        .token_record(token_record.map(|x| x.key()))
+        .system_program(system_program.key())
+        .sysvar_instructions(sysvar_instructions.key())
+        .spl_token_program(token_program.key());
+
+    let mut burn_infos = vec![
+        owner_wallet.to_account_info(),
+        metadata.to_account_info(),
+        edition.to_account_info(),
+        mint.to_account_info(),
+        token_account.to_account_info(),
+        master_accounts.master_edition.to_account_info(),
+        master_accounts.master_edition_mint.to_account_info(),
+        master_accounts.master_edition_token.to_account_info(),
+        master_accounts.edition_marker.to_account_info(),
+        system_program.to_account_info(),
+        sysvar_instructions.to_account_info(),
+        token_program.to_account_info(),
+    ];
+    if let Some(x) = collection_metadata {
+        burn_infos.push(x.to_account_info());
+    }
+    if let Some(x) = token_record {
+        burn_infos.push(x.to_account_info());
+    }
+
+    let ix = burn_builder.amount(1).instruction();
+
+    match authority_seeds {
+        Some(x) => {
+            msg!("invoke_signer");
+            invoke_signed(&ix, &burn_infos, x)?;
+        }
+        None => {
+            msg!("invoke");
+            invoke(&ix, &burn_infos)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edition_marker_changes_every_248_editions() {
+        let mint = Pubkey::new_unique();
+        let marker = |edition_number| {
+            get_edition_marker_address(&mint, edition_number, &TokenStandard::NonFungibleEdition)
+                .unwrap()
+        };
+
+        assert_eq!(marker(247), EditionMarker::find_pda(&mint, "0").0);
+        assert_eq!(marker(248), EditionMarker::find_pda(&mint, "1").0);
+        assert_eq!(marker(249), EditionMarker::find_pda(&mint, "1").0);
+        assert_ne!(marker(247), marker(248));
+    }
+
+    #[test]
+    fn programmable_editions_share_one_marker() {
+        let mint = Pubkey::new_unique();
+        let marker = |edition_number| {
+            get_edition_marker_address(
+                &mint,
+                edition_number,
+                &TokenStandard::ProgrammableNonFungibleEdition,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(marker(1), EditionMarkerV2::find_pda(&mint).0);
+        assert_eq!(marker(247), marker(248));
+        assert_eq!(marker(1), marker(10_000));
+    }
+
+    #[test]
+    fn edition_marker_rejects_non_editions() {
+        let mint = Pubkey::new_unique();
+        assert!(get_edition_marker_address(&mint, 1, &TokenStandard::NonFungible).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{burn, close_account, Burn, CloseAccount};
+use spl_token_2022::{
+    extension::{
+        mint_close_authority::MintCloseAuthority, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Account,
+};
+
+use crate::SharedError;
+
+/// Burns a token-2022 non-fungible, then closes both the holder's token
+/// account and the mint. The rent of both, including the space taken up by
+/// extensions embedded in the mint, goes to rent_recipient.
+///
+/// Accounts owned by an external group program (see token_group_program_id
+/// of create_token_2022_and_metadata), like the group member, stay open.
+///
+/// The mint must have been created with a close authority
+/// (see MintExtensionParams::close_authority).
+pub fn burn_non_fungible_2022<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    owner_wallet: &AccountInfo<'info>,
+    close_authority: &AccountInfo<'info>,
+    rent_recipient: &AccountInfo<'info>,
+    authority_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    if !token_program.key().eq(&spl_token_2022::ID) {
+        return Err(SharedError::UnexpectedTokenType.into());
+    }
+
+    let amount = {
+        let token_account_data = token_account.try_borrow_data()?;
+        let token_account_obj = StateWithExtensions::<Account>::unpack(&token_account_data)?;
+        if token_account_obj.base.mint != mint.key() {
+            return Err(SharedError::BadTokenAccountMint.into());
+        }
+        if token_account_obj.base.owner != owner_wallet.key() {
+            return Err(SharedError::BadTokenAccountOwner.into());
+        }
+        token_account_obj.base.amount
+    };
+
+    {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_obj = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        let mint_close_authority = mint_obj
+            .get_extension::<MintCloseAuthority>()
+            .map_err(|_| SharedError::MissingMintCloseAuthority)?;
+        if Option::<Pubkey>::from(mint_close_authority.close_authority)
+            != Some(close_authority.key())
+        {
+            return Err(SharedError::BadMintCloseAuthority.into());
+        }
+    }
+
+    let signer_seeds = authority_seeds.unwrap_or(&[]);
+
+    if amount > 0 {
+        msg!("Burning {}", amount);
+        burn(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Burn {
+                    mint: mint.to_account_info(),
+                    from: token_account.to_account_info(),
+                    authority: owner_wallet.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+
+    msg!("Closing token account");
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: token_account.to_account_info(),
+            destination: rent_recipient.to_account_info(),
+            authority: owner_wallet.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    msg!("Closing mint");
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: mint.to_account_info(),
+            destination: rent_recipient.to_account_info(),
+            authority: close_authority.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    Ok(())
+}
@@ -0,0 +1,299 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{burn, close_account, Burn, CloseAccount};
+use mpl_token_metadata::types::{Creator, TokenStandard};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+use crate::{
+    create_mint_with_metadata_and_masteredition, create_token_2022_and_metadata, MintAccounts,
+    MintAccounts2022, MintExtensionParams, SharedError, TokenMemberInput,
+};
+
+use super::{
+    assert_in_metaplex_collection, assert_in_token_group, burn_edition, burn_non_fungible_2022,
+    burn_non_pnft, burn_pnft, mint_more, MasterEditionAccounts,
+};
+
+/// Accounts of the asset that is burned during a redemption.
+///
+/// Which of the optional accounts are required depends on the asset:
+/// - legacy NFTs need metadata, edition and collection_metadata
+/// - pNFTs additionally need token_record and payer
+/// - print editions need master_accounts instead of collection_metadata
+/// - token-2022 assets need group, group_member when the member is not
+///   embedded in the mint (see read_token_group_member) and
+///   close_authority + rent_recipient to close the mint. group and
+///   group_member must be owned by token_group_program_id (default
+///   token-2022).
+pub struct BurnInputAccounts<'a, 'info> {
+    pub token_program: &'a AccountInfo<'info>,
+    pub token_account: &'a AccountInfo<'info>,
+    pub owner_wallet: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub metadata: Option<&'a AccountInfo<'info>>,
+    pub edition: Option<&'a AccountInfo<'info>>,
+    pub token_record: Option<&'a AccountInfo<'info>>,
+    pub collection_metadata: Option<&'a AccountInfo<'info>>,
+    pub master_accounts: Option<MasterEditionAccounts<'a, 'info>>,
+    pub group: Option<&'a AccountInfo<'info>>,
+    pub group_member: Option<&'a AccountInfo<'info>>,
+    pub token_group_program_id: Option<Pubkey>,
+    pub close_authority: Option<&'a AccountInfo<'info>>,
+    pub rent_recipient: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub sysvar_instructions: &'a AccountInfo<'info>,
+}
+
+/// Metaplex parameters for the asset minted during a redemption.
+/// See create_mint_with_metadata_and_masteredition.
+pub struct MetaplexMintParams {
+    pub name: String,
+    pub symbol: String,
+    pub seller_fee_basis_points: u16,
+    pub uri: String,
+    pub creators: Option<Vec<Creator>>,
+    pub max_supply: u64,
+    pub is_mutable: bool,
+    pub mint_amount: u64,
+    pub decimals: u8,
+    pub token_standard: TokenStandard,
+}
+
+/// The asset minted during a redemption.
+pub enum RedemptionOutput<'info> {
+    Token2022 {
+        accounts: MintAccounts2022<'info>,
+        decimals: u8,
+        token_metadata: Option<TokenMetadata>,
+        token_member: Option<TokenMemberInput<'info>>,
+        token_group_program_id: Option<Pubkey>,
+        extension_params: Box<MintExtensionParams<'info>>,
+        /// The redeemer, who receives mint_amount tokens in their ATA.
+        destination_owner: AccountInfo<'info>,
+        destination_token_account: AccountInfo<'info>,
+        associated_token_program: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        mint_amount: u64,
+    },
+    Metaplex {
+        accounts: MintAccounts<'info>,
+        params: MetaplexMintParams,
+    },
+}
+
+/// Burns the input asset after checking that it belongs to expected_collection
+/// (a collection mint for Metaplex assets, a group address for token-2022
+/// assets) and then mints the output asset signed with authority_seeds.
+pub fn burn_to_mint<'info>(
+    input: BurnInputAccounts<'_, 'info>,
+    expected_collection: &Pubkey,
+    burn_authority_seeds: Option<&[&[&[u8]]]>,
+    output: RedemptionOutput<'info>,
+    authority_seeds: &[&[u8]],
+) -> Result<()> {
+    let is_metaplex = match input.metadata {
+        Some(x) => !x.data_is_empty(),
+        None => false,
+    };
+
+    if is_metaplex {
+        burn_metaplex_input(&input, expected_collection, burn_authority_seeds)?;
+    } else {
+        burn_token_2022_input(&input, expected_collection, burn_authority_seeds)?;
+    }
+
+    match output {
+        RedemptionOutput::Token2022 {
+            accounts,
+            decimals,
+            token_metadata,
+            token_member,
+            token_group_program_id,
+            extension_params,
+            destination_owner,
+            destination_token_account,
+            associated_token_program,
+            system_program,
+            mint_amount,
+        } => {
+            let nft_mint = accounts.nft_mint.clone();
+            let authority = accounts.authority.clone();
+            let payer = accounts.payer.clone();
+            let token_program = accounts.spl_token_program.clone();
+            create_token_2022_and_metadata(
+                accounts,
+                decimals,
+                token_metadata,
+                None,
+                None,
+                token_member,
+                Some(authority_seeds),
+                None,
+                token_group_program_id,
+                *extension_params,
+                None,
+            )?;
+            mint_more(
+                &nft_mint,
+                &destination_owner,
+                &destination_token_account,
+                &authority,
+                &payer,
+                &token_program,
+                &associated_token_program,
+                &system_program,
+                None,
+                mint_amount,
+                None,
+                // authority is also the freeze authority, so redemptions
+                // work for frozen-by-default mints too
+                Some(&authority),
+                Some(authority_seeds),
+            )?;
+        }
+        RedemptionOutput::Metaplex { accounts, params } => {
+            create_mint_with_metadata_and_masteredition(
+                accounts,
+                authority_seeds,
+                params.name,
+                params.symbol,
+                params.seller_fee_basis_points,
+                params.uri,
+                params.creators,
+                params.max_supply,
+                params.is_mutable,
+                params.mint_amount,
+                params.decimals,
+                params.token_standard,
+                None,
+                None,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn burn_metaplex_input<'info>(
+    input: &BurnInputAccounts<'_, 'info>,
+    expected_collection: &Pubkey,
+    burn_authority_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    let metadata = input.metadata.ok_or(SharedError::MissingMetadata)?;
+
+    let metadata_obj = assert_in_metaplex_collection(input.mint, metadata, expected_collection)?;
+
+    let edition = input
+        .edition
+        .ok_or(SharedError::MissingMasterEditionForNft)?;
+
+    match metadata_obj.token_standard {
+        None | Some(TokenStandard::NonFungible) => burn_non_pnft(
+            input.token_program,
+            input.token_account,
+            input.owner_wallet,
+            edition,
+            input.mint,
+            metadata,
+            input.system_program,
+            input.sysvar_instructions,
+            burn_authority_seeds,
+            input
+                .collection_metadata
+                .ok_or(SharedError::MissingCollectionMetadata)?,
+        ),
+        Some(TokenStandard::ProgrammableNonFungible) => burn_pnft(
+            input.token_program,
+            input.token_account,
+            input.owner_wallet,
+            edition,
+            input.token_record.ok_or(SharedError::MissingTokenRecord)?,
+            input.mint,
+            metadata,
+            input.system_program,
+            input.sysvar_instructions,
+            burn_authority_seeds,
+            input
+                .collection_metadata
+                .ok_or(SharedError::MissingCollectionMetadata)?,
+            input.payer,
+        ),
+        Some(TokenStandard::NonFungibleEdition)
+        | Some(TokenStandard::ProgrammableNonFungibleEdition) => burn_edition(
+            input.token_program,
+            input.token_account,
+            input.owner_wallet,
+            edition,
+            input.token_record,
+            input.mint,
+            metadata,
+            input
+                .master_accounts
+                .as_ref()
+                .ok_or(SharedError::MissingMasterEditionForNft)?,
+            input.system_program,
+            input.sysvar_instructions,
+            burn_authority_seeds,
+            input.collection_metadata,
+        ),
+        _ => Err(SharedError::UnexpectedTokenType.into()),
+    }
+}
+
+fn burn_token_2022_input<'info>(
+    input: &BurnInputAccounts<'_, 'info>,
+    expected_collection: &Pubkey,
+    burn_authority_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    if !input.token_program.key().eq(&spl_token_2022::ID)
+        || !input.mint.owner.eq(&spl_token_2022::ID)
+    {
+        return Err(SharedError::UnexpectedTokenType.into());
+    }
+
+    assert_in_token_group(
+        input.mint,
+        input.group.ok_or(SharedError::MissingGroupMember)?,
+        input.group_member,
+        expected_collection,
+        input.token_group_program_id,
+    )?;
+
+    match (input.close_authority, input.rent_recipient) {
+        (Some(close_authority), Some(rent_recipient)) => burn_non_fungible_2022(
+            input.token_program,
+            input.mint,
+            input.token_account,
+            input.owner_wallet,
+            close_authority,
+            rent_recipient,
+            burn_authority_seeds,
+        ),
+        _ => {
+            // no close authority - burn the token and give the token
+            // account rent back to the holder
+            let signer_seeds = burn_authority_seeds.unwrap_or(&[]);
+            burn(
+                CpiContext::new_with_signer(
+                    input.token_program.to_account_info(),
+                    Burn {
+                        mint: input.mint.to_account_info(),
+                        from: input.token_account.to_account_info(),
+                        authority: input.owner_wallet.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                1,
+            )?;
+            close_account(CpiContext::new_with_signer(
+                input.token_program.to_account_info(),
+                CloseAccount {
+                    account: input.token_account.to_account_info(),
+                    destination: input.owner_wallet.to_account_info(),
+                    authority: input.owner_wallet.to_account_info(),
+                },
+                signer_seeds,
+            ))
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use solana_program::program::{invoke, invoke_signed};
+use spl_token_2022::{
+    extension::{
+        permanent_delegate::PermanentDelegate, BaseStateWithExtensions, StateWithExtensions,
+    },
+    instruction::{burn_checked, transfer_checked},
+    state::Mint,
+};
+
+use crate::{assert_transferable, SharedError};
+
+/// Moves amount tokens out of any holder's account using the mint's
+/// permanent delegate. With a target_token_account the tokens are
+/// transferred there, otherwise they are burned. Non-transferable mints can
+/// only be burned.
+///
+/// remaining_accounts are passed through to transfer_checked, e.g. for
+/// transfer hooks.
+pub fn clawback<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    source_token_account: &AccountInfo<'info>,
+    target_token_account: Option<&AccountInfo<'info>>,
+    permanent_delegate: &AccountInfo<'info>,
+    amount: u64,
+    authority_seeds: Option<&[&[u8]]>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    if !token_program.key().eq(&spl_token_2022::ID) {
+        return Err(SharedError::UnexpectedTokenType.into());
+    }
+
+    let decimals = {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_obj = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let delegate = mint_obj
+            .get_extension::<PermanentDelegate>()
+            .map_err(|_| SharedError::BadPermanentDelegate)?;
+        if Option::<Pubkey>::from(delegate.delegate) != Some(permanent_delegate.key()) {
+            return Err(SharedError::BadPermanentDelegate.into());
+        }
+        mint_obj.base.decimals
+    };
+
+    let (ix, infos) = match target_token_account {
+        Some(target) => {
+            assert_transferable(mint)?;
+            let mut ix = transfer_checked(
+                token_program.key,
+                source_token_account.key,
+                mint.key,
+                target.key,
+                permanent_delegate.key,
+                &[],
+                amount,
+                decimals,
+            )?;
+            remaining_accounts.iter().for_each(|meta| {
+                ix.accounts.push(AccountMeta {
+                    pubkey: meta.key(),
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+            });
+            let infos = [
+                &[
+                    source_token_account.clone(),
+                    mint.clone(),
+                    target.clone(),
+                    permanent_delegate.clone(),
+                ],
+                remaining_accounts,
+            ]
+            .concat();
+            (ix, infos)
+        }
+        None => {
+            let ix = burn_checked(
+                token_program.key,
+                source_token_account.key,
+                mint.key,
+                permanent_delegate.key,
+                &[],
+                amount,
+                decimals,
+            )?;
+            let infos = vec![
+                source_token_account.clone(),
+                mint.clone(),
+                permanent_delegate.clone(),
+            ];
+            (ix, infos)
+        }
+    };
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, infos.as_slice(), &[x])?;
+        }
+        None => {
+            invoke(&ix, infos.as_slice())?;
+        }
+    }
+
+    Ok(())
+}
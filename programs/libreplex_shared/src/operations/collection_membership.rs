@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use mpl_token_metadata::accounts::Metadata;
+
+use crate::{
+    operations::{read_token_group, read_token_group_member},
+    SharedError,
+};
+
+/// Checks that a Metaplex asset is a verified member of expected_collection
+/// and returns its metadata.
+pub fn assert_in_metaplex_collection<'info>(
+    mint: &AccountInfo<'info>,
+    metadata: &AccountInfo<'info>,
+    expected_collection: &Pubkey,
+) -> Result<Metadata> {
+    let expected_metadata = Metadata::find_pda(&mint.key()).0;
+    if expected_metadata != metadata.key() {
+        return Err(SharedError::DerivedKeyInvalid.into());
+    }
+    if !metadata.owner.eq(&mpl_token_metadata::ID) {
+        return Err(SharedError::BadOwner.into());
+    }
+
+    let metadata_obj = Metadata::try_from(metadata)?;
+    if metadata_obj.mint != mint.key() {
+        return Err(SharedError::BadMint.into());
+    }
+    match &metadata_obj.collection {
+        Some(x) if x.verified && x.key == *expected_collection => Ok(metadata_obj),
+        _ => Err(SharedError::NotInExpectedCollection.into()),
+    }
+}
+
+/// Checks that a token-2022 mint is a member of expected_group.
+///
+/// group is the group account itself and group_member the external member
+/// account, if any (see read_token_group_member). Both have to be owned by
+/// token_group_program_id, which defaults to token-2022.
+pub fn assert_in_token_group<'info>(
+    mint: &AccountInfo<'info>,
+    group: &AccountInfo<'info>,
+    group_member: Option<&AccountInfo<'info>>,
+    expected_group: &Pubkey,
+    token_group_program_id: Option<Pubkey>,
+) -> Result<()> {
+    if group.key() != *expected_group {
+        return Err(SharedError::NotInExpectedCollection.into());
+    }
+    // checks the owner and that this is an actual group
+    read_token_group(group, token_group_program_id)?;
+
+    let member = read_token_group_member(mint, group_member, token_group_program_id)?;
+    if member.group != *expected_group {
+        return Err(SharedError::NotInExpectedCollection.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a mint belongs to expected_collection, which is a Metaplex
+/// collection mint for Metaplex assets and a token group for token-2022
+/// assets without Metaplex metadata.
+///
+/// group is required for token-2022 assets.
+pub fn assert_in_collection<'info>(
+    mint: &AccountInfo<'info>,
+    metadata: Option<&AccountInfo<'info>>,
+    group: Option<&AccountInfo<'info>>,
+    group_member: Option<&AccountInfo<'info>>,
+    expected_collection: &Pubkey,
+    token_group_program_id: Option<Pubkey>,
+) -> Result<()> {
+    match metadata.filter(|x| !x.data_is_empty()) {
+        Some(x) => {
+            assert_in_metaplex_collection(mint, x, expected_collection)?;
+        }
+        None => {
+            assert_in_token_group(
+                mint,
+                group.ok_or(SharedError::MissingGroupMember)?,
+                group_member,
+                expected_collection,
+                token_group_program_id,
+            )?;
+        }
+    }
+
+    Ok(())
+}
@@ -13,6 +13,8 @@ use mpl_token_metadata::{
 };
 use solana_program::program::invoke_signed;
 
+use crate::SharedError;
+
 
 /// Accounts to mint an NFT.
 pub struct MintAccounts<'info> {
@@ -30,9 +32,19 @@ pub struct MintAccounts<'info> {
     pub system_program: AccountInfo<'info>,
     pub sysvar_instructions: Option<AccountInfo<'info>>,
     pub rule_set: Option<AccountInfo<'info>>,
+    /// Token record of the new token. Required for pNFTs.
+    pub token_record: Option<AccountInfo<'info>>,
+    /// Required for pNFTs with a rule set.
+    pub authorization_rules_program: Option<AccountInfo<'info>>,
 }
 
 /// Creates the metadata accounts and mint a new token.
+///
+/// token_standard can be NonFungible or ProgrammableNonFungible. For pNFTs
+/// the rule set (if any) is set on the metadata and enforced from then on.
+///
+/// Pass mint_seeds to create the NFT at a program derived mint address
+/// rather than a client-side keypair.
 pub fn create_and_mint(
     accounts: MintAccounts,
     authority_seeds: &[&[u8]],
@@ -42,8 +54,26 @@ pub fn create_and_mint(
     uri: String,
     creators: Vec<Creator>,
     max_supply: u64,
-    is_mutable: bool
+    is_mutable: bool,
+    token_standard: TokenStandard,
+    mint_seeds: Option<&[&[u8]]>,
 ) -> Result<()> {
+    let rule_set_key = match token_standard {
+        TokenStandard::NonFungible => None,
+        TokenStandard::ProgrammableNonFungible => {
+            if accounts.token_record.is_none() {
+                return Err(SharedError::MissingTokenRecord.into());
+            }
+            if accounts.rule_set.is_some() && accounts.authorization_rules_program.is_none() {
+                return Err(SharedError::MissingAuthorizationRulesProgram.into());
+            }
+            accounts.rule_set.as_ref().map(|x| x.key())
+        }
+        _ => {
+            return Err(SharedError::UnexpectedTokenType.into());
+        }
+    };
+
     // create metadata accounts
 
     let sysvar_instructions_info = accounts
@@ -51,16 +81,20 @@ pub fn create_and_mint(
         .as_ref().unwrap();
         
 
-    let create_ix = CreateV1Builder::new()
+    let mut create_ix_builder = CreateV1Builder::new();
+    create_ix_builder
         .metadata(accounts.nft_metadata.key())
-        .mint(accounts.nft_mint.key(), accounts.nft_mint.is_signer)
+        .mint(
+            accounts.nft_mint.key(),
+            accounts.nft_mint.is_signer || mint_seeds.is_some(),
+        )
         .authority(accounts.nft_mint_authority.key())
         .payer(accounts.payer.key())
         .update_authority(accounts.authority_pda.key(), true)
         .master_edition(Some(accounts.nft_master_edition.key()))
         .creators(creators)
         .seller_fee_basis_points(seller_fee_basis_points)
-        .token_standard(TokenStandard::NonFungible)
+        .token_standard(token_standard)
         .is_mutable(is_mutable) // starts off as mutable so we can do an update later
         .name(name)
         .symbol(symbol)
@@ -70,8 +104,11 @@ pub fn create_and_mint(
             PrintSupply::Zero
         } else {
             PrintSupply::Limited(max_supply)
-        })
-        .instruction();
+        });
+    if let Some(x) = rule_set_key {
+        create_ix_builder.rule_set(x);
+    }
+    let create_ix = create_ix_builder.instruction();
 
     let create_infos = vec![
         accounts.nft_metadata.to_account_info(),
@@ -85,7 +122,12 @@ pub fn create_and_mint(
         accounts.spl_token_program.to_account_info(),
     ];
 
-    invoke_signed(&create_ix, &create_infos, &[&authority_seeds])?;
+    let create_signer_seeds = match mint_seeds {
+        Some(x) => vec![authority_seeds, x],
+        None => vec![authority_seeds],
+    };
+
+    invoke_signed(&create_ix, &create_infos, &create_signer_seeds)?;
 
     // mints one token
 
@@ -103,9 +145,17 @@ pub fn create_and_mint(
         .master_edition(Some(accounts.nft_master_edition.key()))
         .mint(accounts.nft_mint.key())
         .payer(accounts.payer.key())
-        .authority(accounts.authority_pda.key());
+        .authority(accounts.authority_pda.key())
+        .token_record(accounts.token_record.as_ref().map(|x| x.key()))
+        .authorization_rules_program(rule_set_key.and(
+            accounts
+                .authorization_rules_program
+                .as_ref()
+                .map(|x| x.key()),
+        ))
+        .authorization_rules(rule_set_key);
 
-    let mint_infos = vec![
+    let mut mint_infos = vec![
         token_info.to_account_info(),
         accounts.nft_owner.to_account_info(),
         accounts.nft_metadata.to_account_info(),
@@ -118,6 +168,17 @@ pub fn create_and_mint(
         accounts.spl_token_program.to_account_info(),
         spl_ata_program_info.to_account_info(),
     ];
+    if let Some(x) = &accounts.token_record {
+        mint_infos.push(x.to_account_info());
+    }
+    if rule_set_key.is_some() {
+        if let Some(x) = &accounts.rule_set {
+            mint_infos.push(x.to_account_info());
+        }
+        if let Some(x) = &accounts.authorization_rules_program {
+            mint_infos.push(x.to_account_info());
+        }
+    }
 
     
     let mint_ix = mint_builder.amount(1).instruction();
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use mpl_token_metadata::{
+    instructions::{CreateV1Builder, MintV1Builder},
+    types::{CollectionDetails, Creator, PrintSupply, TokenStandard},
+};
+use solana_program::program::invoke_signed;
+
+use crate::SharedError;
+
+/// Accounts to create a collection parent NFT.
+pub struct CreateCollectionAccounts<'info> {
+    pub authority_pda: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub collection_mint: AccountInfo<'info>,
+    pub collection_mint_authority: AccountInfo<'info>,
+    pub collection_metadata: AccountInfo<'info>,
+    pub collection_master_edition: AccountInfo<'info>,
+    /// Owner of the collection NFT, usually a PDA. Nothing is minted if None.
+    pub vault: Option<AccountInfo<'info>>,
+    /// Associated token account of the vault for the collection mint.
+    pub vault_token_account: Option<AccountInfo<'info>>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub spl_token_program: AccountInfo<'info>,
+    pub spl_ata_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub sysvar_instructions: AccountInfo<'info>,
+}
+
+/// Creates a sized collection parent NFT with a master edition that cannot
+/// be printed. If a vault is given, the collection NFT is minted into it.
+///
+/// The update authority of the collection is authority_pda, so that the
+/// same seeds can later verify items with verify_collection.
+pub fn create_collection<'info>(
+    accounts: CreateCollectionAccounts<'info>,
+    authority_seeds: &[&[u8]],
+    name: String,
+    symbol: String,
+    seller_fee_basis_points: u16,
+    uri: String,
+    creators: Option<Vec<Creator>>,
+    size: u64,
+    is_mutable: bool,
+) -> Result<()> {
+    let mut create_ix_builder = CreateV1Builder::new();
+    create_ix_builder
+        .metadata(accounts.collection_metadata.key())
+        .mint(
+            accounts.collection_mint.key(),
+            accounts.collection_mint.is_signer,
+        )
+        .authority(accounts.collection_mint_authority.key())
+        .payer(accounts.payer.key())
+        .update_authority(accounts.authority_pda.key(), true)
+        .master_edition(Some(accounts.collection_master_edition.key()))
+        .seller_fee_basis_points(seller_fee_basis_points)
+        .is_mutable(is_mutable)
+        .name(name)
+        .symbol(symbol)
+        .uri(uri)
+        .decimals(0)
+        .token_standard(TokenStandard::NonFungible)
+        .collection_details(CollectionDetails::V1 { size })
+        .print_supply(PrintSupply::Zero);
+    if let Some(x) = creators {
+        create_ix_builder.creators(x);
+    };
+    let create_ix = create_ix_builder.instruction();
+
+    let create_infos = vec![
+        accounts.collection_metadata.to_account_info(),
+        accounts.collection_mint.to_account_info(),
+        accounts.collection_mint_authority.to_account_info(),
+        accounts.payer.to_account_info(),
+        accounts.authority_pda.to_account_info(),
+        accounts.collection_master_edition.to_account_info(),
+        accounts.system_program.to_account_info(),
+        accounts.sysvar_instructions.to_account_info(),
+        accounts.spl_token_program.to_account_info(),
+        accounts.token_metadata_program.to_account_info(),
+    ];
+
+    invoke_signed(&create_ix, &create_infos, &[authority_seeds])?;
+
+    if let Some(vault) = &accounts.vault {
+        let vault_token_account = accounts
+            .vault_token_account
+            .as_ref()
+            .ok_or(SharedError::MissingTokenAccount)?;
+
+        let mint_ix = MintV1Builder::new()
+            .token(vault_token_account.key())
+            .token_owner(Some(vault.key()))
+            .metadata(accounts.collection_metadata.key())
+            .master_edition(Some(accounts.collection_master_edition.key()))
+            .mint(accounts.collection_mint.key())
+            .payer(accounts.payer.key())
+            .authority(accounts.authority_pda.key())
+            .amount(1)
+            .instruction();
+
+        let mint_infos = vec![
+            vault_token_account.to_account_info(),
+            vault.to_account_info(),
+            accounts.collection_metadata.to_account_info(),
+            accounts.collection_master_edition.to_account_info(),
+            accounts.collection_mint.to_account_info(),
+            accounts.payer.to_account_info(),
+            accounts.authority_pda.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.sysvar_instructions.to_account_info(),
+            accounts.spl_token_program.to_account_info(),
+            accounts.spl_ata_program.to_account_info(),
+            accounts.token_metadata_program.to_account_info(),
+        ];
+
+        invoke_signed(&mint_ix, &mint_infos, &[authority_seeds])?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use solana_program::program::{invoke, invoke_signed};
+use spl_token_2022::{
+    amount_to_ui_amount_string_trimmed,
+    extension::{
+        interest_bearing_mint::{instruction::update_rate, InterestBearingConfig},
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint,
+};
+
+use crate::SharedError;
+
+/// Sets a new interest rate (in basis points) on an interest bearing mint.
+pub fn update_interest_rate<'info>(
+    mint: &AccountInfo<'info>,
+    rate_authority: &AccountInfo<'info>,
+    rate: i16,
+    authority_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    let ix = update_rate(
+        &spl_token_2022::ID,
+        &mint.key(),
+        &rate_authority.key(),
+        &[],
+        rate,
+    )?;
+    let account_infos = &[mint.to_account_info(), rate_authority.to_account_info()];
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, account_infos, &[x])?;
+        }
+        None => {
+            invoke(&ix, account_infos)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts a raw amount into its UI amount. For interest bearing mints this
+/// includes the interest accrued up to the current Clock timestamp.
+pub fn get_ui_amount(mint: &AccountInfo, amount: u64) -> Result<String> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint_obj = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let decimals = mint_obj.base.decimals;
+
+    match mint_obj.get_extension::<InterestBearingConfig>() {
+        Ok(config) => {
+            let unix_timestamp = Clock::get()?.unix_timestamp;
+            Ok(config
+                .amount_to_ui_amount(amount, decimals, unix_timestamp)
+                .ok_or(SharedError::NumericalOverflow)?)
+        }
+        Err(_) => Ok(amount_to_ui_amount_string_trimmed(amount, decimals)),
+    }
+}
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use mpl_token_metadata::instructions::MintV1Builder;
+use solana_program::program::{invoke, invoke_signed};
+use spl_token_2022::{extension::StateWithExtensions, instruction::mint_to, state::Mint};
+
+use crate::{
+    operations::{is_token_account_frozen, thaw_token_account},
+    SharedError,
+};
+
+/// Metaplex accounts of a fungible mint, for minting through MintV1.
+pub struct MetaplexMintMoreAccounts<'a, 'info> {
+    pub metadata: &'a AccountInfo<'info>,
+    pub token_metadata_program: &'a AccountInfo<'info>,
+    pub sysvar_instructions: &'a AccountInfo<'info>,
+}
+
+/// Mints amount additional tokens of an existing spl-token or token-2022 mint
+/// to the ATA of destination_owner, creating the ATA if needed.
+///
+/// If max_supply is given, minting fails if the supply would exceed it. With
+/// metaplex accounts the tokens are minted via MintV1, otherwise directly via
+/// the token program.
+///
+/// If thaw_authority (the freeze authority of the mint) is given, a newly
+/// created ATA that starts off frozen (DefaultAccountState) is thawed before
+/// minting, signed with authority_seeds as well.
+pub fn mint_more<'info>(
+    mint: &AccountInfo<'info>,
+    destination_owner: &AccountInfo<'info>,
+    destination_token_account: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    metaplex: Option<MetaplexMintMoreAccounts<'_, 'info>>,
+    amount: u64,
+    max_supply: Option<u64>,
+    thaw_authority: Option<&AccountInfo<'info>>,
+    authority_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    if !mint.owner.eq(&token_program.key()) {
+        return Err(SharedError::UnexpectedTokenType.into());
+    }
+
+    if let Some(max_supply) = max_supply {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_obj = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let new_supply = mint_obj
+            .base
+            .supply
+            .checked_add(amount)
+            .ok_or(SharedError::NumericalOverflow)?;
+        if new_supply > max_supply {
+            return Err(SharedError::MaxSupplyExceeded.into());
+        }
+    }
+
+    let expected_token_account =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &destination_owner.key(),
+            &mint.key(),
+            &token_program.key(),
+        );
+    if expected_token_account != destination_token_account.key() {
+        return Err(SharedError::InvalidTokenAccount.into());
+    }
+
+    // MintV1 creates the token account itself, unless it has to be thawed
+    // before minting
+    if metaplex.is_none() || thaw_authority.is_some() {
+        let is_new_token_account = destination_token_account.data_is_empty();
+        anchor_spl::associated_token::create_idempotent(CpiContext::new(
+            associated_token_program.to_account_info(),
+            anchor_spl::associated_token::Create {
+                payer: payer.to_account_info(),
+                associated_token: destination_token_account.to_account_info(),
+                authority: destination_owner.to_account_info(),
+                mint: mint.to_account_info(),
+                system_program: system_program.to_account_info(),
+                token_program: token_program.to_account_info(),
+            },
+        ))?;
+
+        if let Some(x) = thaw_authority {
+            if is_new_token_account && is_token_account_frozen(destination_token_account)? {
+                msg!("Thawing new token account");
+                let thaw_seeds = authority_seeds.map(|seeds| [seeds]);
+                thaw_token_account(
+                    token_program,
+                    destination_token_account,
+                    mint,
+                    x,
+                    thaw_seeds.as_ref().map(|seeds| seeds.as_slice()),
+                )?;
+            }
+        }
+    }
+
+    let (ix, account_infos) = match metaplex {
+        Some(x) => {
+            // MintV1 creates the token account itself
+            let ix = MintV1Builder::new()
+                .token(destination_token_account.key())
+                .token_owner(Some(destination_owner.key()))
+                .metadata(x.metadata.key())
+                .mint(mint.key())
+                .authority(mint_authority.key())
+                .payer(payer.key())
+                .spl_token_program(token_program.key())
+                .amount(amount)
+                .instruction();
+            let account_infos = vec![
+                destination_token_account.to_account_info(),
+                destination_owner.to_account_info(),
+                x.metadata.to_account_info(),
+                mint.to_account_info(),
+                mint_authority.to_account_info(),
+                payer.to_account_info(),
+                system_program.to_account_info(),
+                x.sysvar_instructions.to_account_info(),
+                token_program.to_account_info(),
+                associated_token_program.to_account_info(),
+                x.token_metadata_program.to_account_info(),
+            ];
+            (ix, account_infos)
+        }
+        None => {
+            let ix = mint_to(
+                &token_program.key(),
+                &mint.key(),
+                &destination_token_account.key(),
+                &mint_authority.key(),
+                &[],
+                amount,
+            )?;
+            let account_infos = vec![
+                mint.to_account_info(),
+                destination_token_account.to_account_info(),
+                mint_authority.to_account_info(),
+                token_program.to_account_info(),
+            ];
+            (ix, account_infos)
+        }
+    };
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, account_infos.as_slice(), &[x])?;
+        }
+        None => {
+            invoke(&ix, account_infos.as_slice())?;
+        }
+    }
+
+    Ok(())
+}
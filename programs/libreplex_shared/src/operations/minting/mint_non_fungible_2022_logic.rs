@@ -1,18 +1,62 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{mint_to, set_authority, MintTo, SetAuthority},
+    token_interface::{mint_to, MintTo},
+};
+use solana_program::program::invoke_signed;
+use spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    instruction::{set_authority, AuthorityType},
+    state::{Account, Mint},
 };
 
-use crate::SharedError;
+use crate::{
+    operations::{is_token_account_frozen, thaw_token_account},
+    SharedError,
+};
 // use libreplex_shared::sysvar_instructions_program;
 
+/// What happens to an authority once minting has finished.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AuthorityAction {
+    Keep,
+    Revoke,
+    Transfer(Pubkey),
+}
 
+/// Authority handling for mint_non_fungible_2022_logic.
+///
+/// The default revokes the mint authority and keeps everything else, as
+/// removing the freeze authority kills things like escrowless staking.
+/// Pointer authorities are only touched if the mint has that extension.
+#[derive(Clone, Copy)]
+pub struct MintAuthorityPolicy {
+    pub mint: AuthorityAction,
+    pub freeze: AuthorityAction,
+    pub metadata_pointer: AuthorityAction,
+    pub group_pointer: AuthorityAction,
+    /// Thaw the token account if it was created here and starts off frozen
+    /// (DefaultAccountState). authority must be the freeze authority.
+    pub thaw_new_token_account: bool,
+}
 
+impl Default for MintAuthorityPolicy {
+    fn default() -> Self {
+        MintAuthorityPolicy {
+            mint: AuthorityAction::Revoke,
+            freeze: AuthorityAction::Keep,
+            metadata_pointer: AuthorityAction::Keep,
+            group_pointer: AuthorityAction::Keep,
+            thaw_new_token_account: false,
+        }
+    }
+}
 
-
-
-
+/// Mints amount tokens to non_fungible_token_account and then applies
+/// authority_policy.
+///
+/// The token account is either the minter's ATA (created if needed) or an
+/// existing token account for this mint owned by the minter.
 pub fn mint_non_fungible_2022_logic<'a>(
     non_fungible_mint: &AccountInfo<'a>,
     non_fungible_token_account: &AccountInfo<'a>,
@@ -23,6 +67,8 @@ pub fn mint_non_fungible_2022_logic<'a>(
     token_program: &UncheckedAccount<'a>,
     authority: &AccountInfo<'a>,
     deployment_seeds: &[&[u8]],
+    amount: u64,
+    authority_policy: &MintAuthorityPolicy,
 ) -> Result<()> {
     msg!("MINT NON-FUNGIBLE {}", token_program.key());
     let expected_token_account =
@@ -31,26 +77,51 @@ pub fn mint_non_fungible_2022_logic<'a>(
             &non_fungible_mint.key(),
             &token_program.key(),
         );
-    if expected_token_account != non_fungible_token_account.key() {
-        return Err(SharedError::InvalidTokenAccount.into());
-    }
-    if non_fungible_token_account
-        .to_account_info()
-        .data_is_empty()
-    {
-        msg!("{}", payer.key());
-        anchor_spl::associated_token::create(CpiContext::new_with_signer(
-            associated_token_program.to_account_info(),
-            anchor_spl::associated_token::Create {
-                payer: payer.to_account_info(),
-                associated_token: non_fungible_token_account.to_account_info(),
-                authority: minter.clone(),
-                mint: non_fungible_mint.clone(),
-                system_program: system_program.to_account_info(),
-                token_program: token_program.to_account_info(),
-            },
-            &[deployment_seeds]
-        ))?;
+    if expected_token_account == non_fungible_token_account.key() {
+        if non_fungible_token_account
+            .to_account_info()
+            .data_is_empty()
+        {
+            msg!("{}", payer.key());
+            anchor_spl::associated_token::create(CpiContext::new_with_signer(
+                associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: payer.to_account_info(),
+                    associated_token: non_fungible_token_account.to_account_info(),
+                    authority: minter.clone(),
+                    mint: non_fungible_mint.clone(),
+                    system_program: system_program.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                },
+                &[deployment_seeds]
+            ))?;
+
+            if authority_policy.thaw_new_token_account
+                && is_token_account_frozen(non_fungible_token_account)?
+            {
+                msg!("Thawing new token account");
+                thaw_token_account(
+                    token_program,
+                    non_fungible_token_account,
+                    non_fungible_mint,
+                    authority,
+                    Some(&[deployment_seeds]),
+                )?;
+            }
+        }
+    } else {
+        // not an ATA - must be an existing account for this mint
+        if !non_fungible_token_account.owner.eq(&token_program.key()) {
+            return Err(SharedError::InvalidTokenAccount.into());
+        }
+        let token_account_data = non_fungible_token_account.try_borrow_data()?;
+        let token_account_obj = StateWithExtensions::<Account>::unpack(&token_account_data)?;
+        if token_account_obj.base.mint != non_fungible_mint.key() {
+            return Err(SharedError::BadTokenAccountMint.into());
+        }
+        if token_account_obj.base.owner != minter.key() {
+            return Err(SharedError::BadTokenAccountOwner.into());
+        }
     }
 
     if token_program.key().eq(&spl_token_2022::ID) {
@@ -65,41 +136,48 @@ pub fn mint_non_fungible_2022_logic<'a>(
                 },
                 &[deployment_seeds],
             ),
-            1,
+            amount,
         )?;
-        // no longer removing freeze auth etc as this 
-        // kills things like escrowless staking. keeping update 
-        // auth too
 
-        // msg!("Removing freeze auth - non_fungible");
+        let extension_types = {
+            let mint_data = non_fungible_mint.try_borrow_data()?;
+            StateWithExtensions::<Mint>::unpack(&mint_data)?.get_extension_types()?
+        };
 
-        // set_authority(
-        //     CpiContext::new_with_signer(
-        //         token_program.to_account_info(),
-        //         SetAuthority {
-        //             current_authority: authority.clone(),
-        //             account_or_mint: non_fungible_mint.clone(),
-        //         },
-        //         &[deployment_seeds]
-        //     ),
-        //     anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType::FreezeAccount,
-        //     None,
-        // )?;
+        let mut authority_updates = vec![
+            (authority_policy.mint, AuthorityType::MintTokens),
+            (authority_policy.freeze, AuthorityType::FreezeAccount),
+        ];
+        if extension_types.contains(&ExtensionType::MetadataPointer) {
+            authority_updates.push((
+                authority_policy.metadata_pointer,
+                AuthorityType::MetadataPointer,
+            ));
+        }
+        if extension_types.contains(&ExtensionType::GroupPointer) {
+            authority_updates.push((authority_policy.group_pointer, AuthorityType::GroupPointer));
+        }
 
-        msg!("Removing mint authority");
-        // ok we are at max mint
-        set_authority(
-            CpiContext::new_with_signer(
-                token_program.to_account_info(),
-                SetAuthority {
-                    current_authority: authority.clone(),
-                    account_or_mint: non_fungible_mint.clone(),
-                },
-                &[deployment_seeds]
-            ),
-            anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType::MintTokens,
-            None,
-        )?;
+        for (action, authority_type) in authority_updates {
+            let new_authority = match action {
+                AuthorityAction::Keep => continue,
+                AuthorityAction::Revoke => None,
+                AuthorityAction::Transfer(x) => Some(x),
+            };
+            msg!("Updating authority {:?}", authority_type);
+            invoke_signed(
+                &set_authority(
+                    &spl_token_2022::ID,
+                    &non_fungible_mint.key(),
+                    new_authority.as_ref(),
+                    authority_type,
+                    &authority.key(),
+                    &[],
+                )?,
+                &[non_fungible_mint.clone(), authority.clone()],
+                &[deployment_seeds],
+            )?;
+        }
         msg!("done");
     } else {
         panic!("This method is only compatible with token-2022")
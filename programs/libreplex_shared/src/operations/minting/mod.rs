@@ -1,2 +1,5 @@
 pub mod mint_non_fungible_2022_logic;
-pub use mint_non_fungible_2022_logic::*;
\ No newline at end of file
+pub use mint_non_fungible_2022_logic::*;
+
+pub mod mint_more;
+pub use mint_more::*;
\ No newline at end of file
@@ -1,6 +1,15 @@
 // pub mod create_token_account;
 // pub use create_token_account::*;
 
+pub mod asset_info;
+pub use asset_info::*;
+
+pub mod collection_membership;
+pub use collection_membership::*;
+
+pub mod royalties;
+pub use royalties::*;
+
 pub mod transfer_any_asset;
 pub use transfer_any_asset::*;
 
@@ -10,6 +19,12 @@ pub use minting::*;
 pub mod create_and_mint;
 pub use create_and_mint::*;
 
+pub mod create_collection;
+pub use create_collection::*;
+
+pub mod print_edition;
+pub use print_edition::*;
+
 pub mod create_and_verify_ata;
 pub use create_and_verify_ata::*;
 
@@ -35,5 +50,35 @@ pub use burn_pnft::*;
 pub mod burn_non_pnft;
 pub use burn_non_pnft::*;
 
+pub mod burn_edition;
+pub use burn_edition::*;
+
+pub mod burn_non_fungible_2022;
+pub use burn_non_fungible_2022::*;
+
+pub mod burn_to_mint;
+pub use burn_to_mint::*;
+
+pub mod update_token_2022_metadata;
+pub use update_token_2022_metadata::*;
+
+pub mod update_metaplex_metadata;
+pub use update_metaplex_metadata::*;
+
+pub mod verify_creators;
+pub use verify_creators::*;
+
+pub mod account_state;
+pub use account_state::*;
+
+pub mod clawback;
+pub use clawback::*;
+
+pub mod interest_bearing;
+pub use interest_bearing::*;
+
+pub mod token_group;
+pub use token_group::*;
+
 pub mod wrap_sol;
 pub use wrap_sol::*;
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::PrintV1Builder,
+    types::TokenStandard,
+};
+use solana_program::program::invoke_signed;
+
+use crate::{operations::get_edition_marker_address, SharedError};
+
+/// Accounts to print an edition from a master edition held by a PDA.
+pub struct PrintEditionAccounts<'info> {
+    /// Owner of master_token_account.
+    pub authority_pda: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub edition_owner: AccountInfo<'info>,
+    pub edition_mint: AccountInfo<'info>,
+    pub edition_mint_authority: AccountInfo<'info>,
+    pub edition_metadata: AccountInfo<'info>,
+    pub edition: AccountInfo<'info>,
+    pub edition_token_account: AccountInfo<'info>,
+    /// Required when printing from a pNFT master edition.
+    pub edition_token_record: Option<AccountInfo<'info>>,
+    pub edition_marker: AccountInfo<'info>,
+    pub master_edition: AccountInfo<'info>,
+    pub master_edition_mint: AccountInfo<'info>,
+    pub master_metadata: AccountInfo<'info>,
+    pub master_token_account: AccountInfo<'info>,
+    pub master_update_authority: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub spl_token_program: AccountInfo<'info>,
+    pub spl_ata_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub sysvar_instructions: AccountInfo<'info>,
+}
+
+/// Prints the next numbered edition of a master edition and returns its
+/// edition number.
+///
+/// NonFungible masters print NonFungibleEditions, ProgrammableNonFungible
+/// masters print ProgrammableNonFungibleEditions.
+pub fn print_edition<'info>(
+    accounts: PrintEditionAccounts<'info>,
+    authority_seeds: &[&[u8]],
+) -> Result<u64> {
+    let master_metadata_obj = Metadata::try_from(&accounts.master_metadata)?;
+    let edition_token_standard = match master_metadata_obj.token_standard {
+        Some(TokenStandard::NonFungible) | None => TokenStandard::NonFungibleEdition,
+        Some(TokenStandard::ProgrammableNonFungible) => {
+            if accounts.edition_token_record.is_none() {
+                return Err(SharedError::MissingTokenRecord.into());
+            }
+            TokenStandard::ProgrammableNonFungibleEdition
+        }
+        _ => {
+            return Err(SharedError::UnexpectedTokenType.into());
+        }
+    };
+
+    let edition_number = {
+        let master_edition_data = accounts.master_edition.try_borrow_data()?;
+        let master_edition_obj = MasterEdition::safe_deserialize(&master_edition_data)?;
+        let edition_number = master_edition_obj
+            .supply
+            .checked_add(1)
+            .ok_or(SharedError::NumericalOverflow)?;
+        if let Some(max_supply) = master_edition_obj.max_supply {
+            if edition_number > max_supply {
+                return Err(SharedError::EditionSupplyExhausted.into());
+            }
+        }
+        edition_number
+    };
+
+    let expected_edition_marker = get_edition_marker_address(
+        &accounts.master_edition_mint.key(),
+        edition_number,
+        &edition_token_standard,
+    )?;
+    if accounts.edition_marker.key() != expected_edition_marker {
+        return Err(SharedError::BadEditionMarker.into());
+    }
+
+    let print_ix = PrintV1Builder::new()
+        .edition_metadata(accounts.edition_metadata.key())
+        .edition(accounts.edition.key())
+        .edition_mint(accounts.edition_mint.key(), accounts.edition_mint.is_signer)
+        .edition_token_account_owner(accounts.edition_owner.key())
+        .edition_token_account(accounts.edition_token_account.key())
+        .edition_mint_authority(accounts.edition_mint_authority.key())
+        .edition_token_record(accounts.edition_token_record.as_ref().map(|x| x.key()))
+        .master_edition(accounts.master_edition.key())
+        .edition_marker_pda(accounts.edition_marker.key())
+        .payer(accounts.payer.key())
+        .master_token_account_owner(accounts.authority_pda.key())
+        .master_token_account(accounts.master_token_account.key())
+        .master_metadata(accounts.master_metadata.key())
+        .update_authority(accounts.master_update_authority.key())
+        .spl_token_program(accounts.spl_token_program.key())
+        .edition_number(edition_number)
+        .instruction();
+
+    let mut print_infos = vec![
+        accounts.edition_metadata.to_account_info(),
+        accounts.edition.to_account_info(),
+        accounts.edition_mint.to_account_info(),
+        accounts.edition_owner.to_account_info(),
+        accounts.edition_token_account.to_account_info(),
+        accounts.edition_mint_authority.to_account_info(),
+        accounts.master_edition.to_account_info(),
+        accounts.edition_marker.to_account_info(),
+        accounts.payer.to_account_info(),
+        accounts.authority_pda.to_account_info(),
+        accounts.master_token_account.to_account_info(),
+        accounts.master_metadata.to_account_info(),
+        accounts.master_update_authority.to_account_info(),
+        accounts.spl_token_program.to_account_info(),
+        accounts.spl_ata_program.to_account_info(),
+        accounts.sysvar_instructions.to_account_info(),
+        accounts.system_program.to_account_info(),
+        accounts.token_metadata_program.to_account_info(),
+    ];
+    if let Some(x) = &accounts.edition_token_record {
+        print_infos.push(x.to_account_info());
+    }
+
+    invoke_signed(&print_ix, &print_infos, &[authority_seeds])?;
+
+    Ok(edition_number)
+}
@@ -0,0 +1,267 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use mpl_token_metadata::accounts::Metadata;
+use spl_token_2022::{
+    extension::{
+        metadata_pointer::MetadataPointer, transfer_fee::TransferFeeConfig,
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint,
+};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+use crate::SharedError;
+
+/// Additional metadata key holding the royalty in basis points (0-10000).
+pub const ROYALTY_BASIS_POINTS_KEY: &str = "royalty_basis_points";
+
+/// Prefix of the additional metadata keys holding the creators, as
+/// "creator:<pubkey>" with the share (0-100) as value. The shares of all
+/// creators must add up to 100.
+pub const ROYALTY_CREATOR_KEY_PREFIX: &str = "creator:";
+
+/// Where the royalties of an asset were read from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RoyaltySource {
+    None,
+    Metaplex,
+    Token2022Metadata,
+    /// The transfer fee of the mint is used as royalty.
+    TransferFee,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoyaltyShare {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoyaltyInfo {
+    pub basis_points: u16,
+    pub creators: Vec<RoyaltyShare>,
+    pub source: RoyaltySource,
+}
+
+/// Returns the royalty basis points and creator shares of any asset.
+///
+/// Metaplex metadata (if given and not empty) takes precedence, followed by
+/// ROYALTY_BASIS_POINTS_KEY in token-2022 embedded metadata and finally the
+/// transfer fee of the current epoch, paid out to the withdraw withheld
+/// authority.
+pub fn get_royalties<'info>(
+    mint: &AccountInfo<'info>,
+    metadata: Option<&AccountInfo<'info>>,
+) -> Result<RoyaltyInfo> {
+    if let Some(x) = metadata.filter(|x| !x.data_is_empty()) {
+        if x.key() != Metadata::find_pda(&mint.key()).0 {
+            return Err(SharedError::DerivedKeyInvalid.into());
+        }
+        let metadata_obj = Metadata::try_from(x)?;
+        return Ok(RoyaltyInfo {
+            basis_points: metadata_obj.seller_fee_basis_points,
+            creators: metadata_obj
+                .creators
+                .unwrap_or_default()
+                .iter()
+                .map(|creator| RoyaltyShare {
+                    address: creator.address,
+                    share: creator.share,
+                })
+                .collect(),
+            source: RoyaltySource::Metaplex,
+        });
+    }
+
+    if !mint.owner.eq(&spl_token_2022::ID) {
+        return Ok(RoyaltyInfo {
+            basis_points: 0,
+            creators: vec![],
+            source: RoyaltySource::None,
+        });
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_obj = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+
+    let has_embedded_metadata = mint_obj
+        .get_extension::<MetadataPointer>()
+        .map(|x| Option::<Pubkey>::from(x.metadata_address) == Some(mint.key()))
+        .unwrap_or(false);
+    if has_embedded_metadata {
+        let token_metadata = mint_obj.get_variable_len_extension::<TokenMetadata>()?;
+        if let Some(x) = parse_royalty_fields(&token_metadata.additional_metadata)? {
+            return Ok(x);
+        }
+    }
+
+    if let Ok(transfer_fee_config) = mint_obj.get_extension::<TransferFeeConfig>() {
+        let epoch = Clock::get()?.epoch;
+        let basis_points: u16 = transfer_fee_config
+            .get_epoch_fee(epoch)
+            .transfer_fee_basis_points
+            .into();
+        let creators = Option::<Pubkey>::from(transfer_fee_config.withdraw_withheld_authority)
+            .map(|address| vec![RoyaltyShare { address, share: 100 }])
+            .unwrap_or_default();
+        return Ok(RoyaltyInfo {
+            basis_points,
+            creators,
+            source: RoyaltySource::TransferFee,
+        });
+    }
+
+    Ok(RoyaltyInfo {
+        basis_points: 0,
+        creators: vec![],
+        source: RoyaltySource::None,
+    })
+}
+
+/// Reads the royalty from token-2022 additional metadata, see
+/// ROYALTY_BASIS_POINTS_KEY and ROYALTY_CREATOR_KEY_PREFIX. Returns None if
+/// there is no ROYALTY_BASIS_POINTS_KEY.
+pub fn parse_royalty_fields(
+    additional_metadata: &[(String, String)],
+) -> Result<Option<RoyaltyInfo>> {
+    let basis_points = match additional_metadata
+        .iter()
+        .find(|(key, _)| key == ROYALTY_BASIS_POINTS_KEY)
+    {
+        Some((_, value)) => value
+            .parse::<u16>()
+            .ok()
+            .filter(|x| *x <= 10_000)
+            .ok_or(SharedError::BadRoyaltyField)?,
+        None => return Ok(None),
+    };
+
+    let mut creators = vec![];
+    let mut total_share: u16 = 0;
+    for (key, value) in additional_metadata.iter() {
+        if let Some(address) = key.strip_prefix(ROYALTY_CREATOR_KEY_PREFIX) {
+            let share = RoyaltyShare {
+                address: Pubkey::from_str(address).map_err(|_| SharedError::BadRoyaltyField)?,
+                share: value.parse().map_err(|_| SharedError::BadRoyaltyField)?,
+            };
+            total_share = total_share
+                .checked_add(share.share.into())
+                .ok_or(SharedError::BadRoyaltyField)?;
+            creators.push(share);
+        }
+    }
+    if !creators.is_empty() && total_share != 100 {
+        return Err(SharedError::BadRoyaltyField.into());
+    }
+
+    Ok(Some(RoyaltyInfo {
+        basis_points,
+        creators,
+        source: RoyaltySource::Token2022Metadata,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    fn creator_key(address: &Pubkey) -> String {
+        format!("{}{}", ROYALTY_CREATOR_KEY_PREFIX, address)
+    }
+
+    #[test]
+    fn parses_basis_points_and_creators() {
+        let creator_1 = Pubkey::new_unique();
+        let creator_2 = Pubkey::new_unique();
+        let royalty = parse_royalty_fields(&[
+            field(ROYALTY_BASIS_POINTS_KEY, "500"),
+            field(&creator_key(&creator_1), "70"),
+            field(&creator_key(&creator_2), "30"),
+            // unrelated attribute keyed by an address
+            field(&Pubkey::new_unique().to_string(), "not a share"),
+        ])
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(royalty.basis_points, 500);
+        assert_eq!(
+            royalty.creators,
+            vec![
+                RoyaltyShare {
+                    address: creator_1,
+                    share: 70
+                },
+                RoyaltyShare {
+                    address: creator_2,
+                    share: 30
+                },
+            ]
+        );
+        assert_eq!(royalty.source, RoyaltySource::Token2022Metadata);
+    }
+
+    #[test]
+    fn allows_no_creators() {
+        let royalty = parse_royalty_fields(&[field(ROYALTY_BASIS_POINTS_KEY, "0")])
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(royalty.basis_points, 0);
+        assert!(royalty.creators.is_empty());
+    }
+
+    #[test]
+    fn missing_basis_points_is_none() {
+        let creator = Pubkey::new_unique();
+        assert!(parse_royalty_fields(&[]).unwrap().is_none());
+        assert!(
+            parse_royalty_fields(&[field(&creator_key(&creator), "100")])
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_basis_points() {
+        for value in ["", "abc", "-1", "10001", "70000"] {
+            assert!(parse_royalty_fields(&[field(ROYALTY_BASIS_POINTS_KEY, value)]).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_creators() {
+        let creator = Pubkey::new_unique();
+        let bad_fields = [
+            field(&creator_key(&creator), "abc"),
+            field(&creator_key(&creator), "256"),
+            field(
+                &format!("{}not-a-pubkey", ROYALTY_CREATOR_KEY_PREFIX),
+                "100",
+            ),
+        ];
+        for bad_field in bad_fields {
+            assert!(
+                parse_royalty_fields(&[field(ROYALTY_BASIS_POINTS_KEY, "500"), bad_field]).is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_shares_not_adding_up_to_100() {
+        let creator_1 = Pubkey::new_unique();
+        let creator_2 = Pubkey::new_unique();
+        for (share_1, share_2) in [("50", "40"), ("60", "50"), ("255", "255")] {
+            assert!(parse_royalty_fields(&[
+                field(ROYALTY_BASIS_POINTS_KEY, "500"),
+                field(&creator_key(&creator_1), share_1),
+                field(&creator_key(&creator_2), share_2),
+            ])
+            .is_err());
+        }
+    }
+}
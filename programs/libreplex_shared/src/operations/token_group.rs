@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use solana_program::program::{invoke, invoke_signed};
+use spl_token_2022::{
+    extension::{
+        group_member_pointer::GroupMemberPointer, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint,
+};
+use spl_token_group_interface::{
+    instruction::{update_group_authority, update_group_max_size},
+    state::{TokenGroup, TokenGroupMember},
+};
+use spl_type_length_value::state::{TlvState, TlvStateBorrowed};
+
+use crate::SharedError;
+
+/// Reads a token group, either embedded in a token-2022 mint or stored in a
+/// standalone account of an external group program.
+///
+/// token_group_program_id defaults to token-2022. The group account must be
+/// owned by it.
+pub fn read_token_group(
+    group: &AccountInfo,
+    token_group_program_id: Option<Pubkey>,
+) -> Result<TokenGroup> {
+    let program_id = token_group_program_id.unwrap_or(spl_token_2022::ID);
+    if !group.owner.eq(&program_id) {
+        return Err(SharedError::BadOwner.into());
+    }
+
+    let group_data = group.try_borrow_data()?;
+    if program_id.eq(&spl_token_2022::ID) {
+        let mint_obj = StateWithExtensions::<Mint>::unpack(&group_data)?;
+        Ok(*mint_obj.get_extension::<TokenGroup>()?)
+    } else {
+        Ok(*TlvStateBorrowed::unpack(&group_data)?.get_first_value::<TokenGroup>()?)
+    }
+}
+
+/// Returns the current size and max size of a token group.
+pub fn get_token_group_size(
+    group: &AccountInfo,
+    token_group_program_id: Option<Pubkey>,
+) -> Result<(u32, u32)> {
+    let token_group = read_token_group(group, token_group_program_id)?;
+    Ok((token_group.size.into(), token_group.max_size.into()))
+}
+
+/// Reads the group membership of a token-2022 mint.
+///
+/// The membership is embedded in the mint, or stored in group_member, which
+/// must then be owned by token_group_program_id (default token-2022). The
+/// latter also applies when the GroupMemberPointer points to the mint itself
+/// but the member was written by an external group program, as
+/// create_token_2022_and_metadata does.
+pub fn read_token_group_member(
+    mint: &AccountInfo,
+    group_member: Option<&AccountInfo>,
+    token_group_program_id: Option<Pubkey>,
+) -> Result<TokenGroupMember> {
+    if !mint.owner.eq(&spl_token_2022::ID) {
+        return Err(SharedError::UnexpectedTokenType.into());
+    }
+    let program_id = token_group_program_id.unwrap_or(spl_token_2022::ID);
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_obj = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let member_address = Option::<Pubkey>::from(
+        mint_obj
+            .get_extension::<GroupMemberPointer>()
+            .map_err(|_| SharedError::MissingGroupMember)?
+            .member_address,
+    )
+    .ok_or(SharedError::MissingGroupMember)?;
+
+    if member_address == mint.key() {
+        if let Ok(member) = mint_obj.get_extension::<TokenGroupMember>() {
+            return Ok(*member);
+        }
+    }
+
+    // token-2022 only ever stores members on the mint itself
+    if program_id.eq(&spl_token_2022::ID) {
+        return Err(SharedError::MissingGroupMember.into());
+    }
+
+    let group_member = group_member.ok_or(SharedError::MissingGroupMember)?;
+    if member_address != mint.key() && group_member.key() != member_address {
+        return Err(SharedError::MissingGroupMember.into());
+    }
+    if !group_member.owner.eq(&program_id) {
+        return Err(SharedError::BadOwner.into());
+    }
+
+    let member_data = group_member.try_borrow_data()?;
+    let member_obj =
+        *TlvStateBorrowed::unpack(&member_data)?.get_first_value::<TokenGroupMember>()?;
+    if member_obj.mint != mint.key() {
+        return Err(SharedError::BadMint.into());
+    }
+
+    Ok(member_obj)
+}
+
+/// Sets the max size of a token group. The new max size cannot be less than
+/// the current size.
+///
+/// token_group_program_id defaults to token-2022 (the native group
+/// implementation, where the group lives on the mint).
+pub fn update_token_group_max_size<'info>(
+    group: &AccountInfo<'info>,
+    update_authority: &AccountInfo<'info>,
+    max_size: u32,
+    token_group_program_id: Option<Pubkey>,
+    authority_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    let program_id = token_group_program_id.unwrap_or(spl_token_2022::ID);
+
+    let ix = update_group_max_size(&program_id, &group.key(), &update_authority.key(), max_size);
+    let account_infos = &[group.to_account_info(), update_authority.to_account_info()];
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, account_infos, &[x])?;
+        }
+        None => {
+            invoke(&ix, account_infos)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Rotates the update authority of a token group. Passing None renounces it,
+/// after which the group can no longer be updated and no new members can be
+/// added.
+pub fn update_token_group_authority<'info>(
+    group: &AccountInfo<'info>,
+    update_authority: &AccountInfo<'info>,
+    new_authority: Option<Pubkey>,
+    token_group_program_id: Option<Pubkey>,
+    authority_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    let program_id = token_group_program_id.unwrap_or(spl_token_2022::ID);
+
+    let ix = update_group_authority(
+        &program_id,
+        &group.key(),
+        &update_authority.key(),
+        new_authority,
+    );
+    let account_infos = &[group.to_account_info(), update_authority.to_account_info()];
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, account_infos, &[x])?;
+        }
+        None => {
+            invoke(&ix, account_infos)?;
+        }
+    }
+
+    Ok(())
+}
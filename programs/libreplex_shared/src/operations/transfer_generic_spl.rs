@@ -6,7 +6,11 @@ use solana_program::
 ;
 use transfer_pnft::MetaplexProgrammableTransferExtraAccounts;
 
-use crate::{operations::transfer_pnft, SharedError};
+use crate::{
+    assert_transferable,
+    operations::{is_token_account_frozen, thaw_token_account, transfer_pnft},
+    SharedError,
+};
 
 pub fn transfer_generic_spl<'info>(
     token_program: &AccountInfo<'info>,
@@ -21,8 +25,13 @@ pub fn transfer_generic_spl<'info>(
     payer: &AccountInfo<'info>,
     decimals: u8,
     amount: u64,
+    // if specified, a newly created target account that starts off frozen
+    // (DefaultAccountState) is thawed by this freeze authority
+    thaw_authority: Option<&AccountInfo<'info>>,
     remaining_accounts: &'info [AccountInfo<'info>],
 ) -> Result<()> {
+    assert_transferable(mint)?;
+
     msg!("{}", token_program.key());
     let expected_token_account =
         anchor_spl::associated_token::get_associated_token_address_with_program_id(
@@ -50,6 +59,19 @@ pub fn transfer_generic_spl<'info>(
                 token_program: token_program.clone(),
             },
         ))?;
+
+        if let Some(x) = thaw_authority {
+            if is_token_account_frozen(target_token_account)? {
+                msg!("Thawing token account");
+                thaw_token_account(
+                    token_program,
+                    target_token_account,
+                    mint,
+                    x,
+                    authority_seeds,
+                )?;
+            }
+        }
     }
 
     let extra_accounts = MetaplexProgrammableTransferExtraAccounts::new(
@@ -2,7 +2,7 @@
 
 use anchor_lang::prelude::*;
 
-use crate::SharedError;
+use crate::{assert_transferable, SharedError};
 
 pub fn transfer_non_pnft<'info>(
     token_program: &AccountInfo<'info>,
@@ -17,7 +17,8 @@ pub fn transfer_non_pnft<'info>(
     payer: &AccountInfo<'info>,
     amount: u64
 ) -> Result<()> {
-    
+    assert_transferable(mint)?;
+
     let expected_token_account = anchor_spl::associated_token::get_associated_token_address(
         &target_wallet.key(), &mint.key());
 
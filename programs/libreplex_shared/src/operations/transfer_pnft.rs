@@ -11,7 +11,7 @@ use mpl_token_metadata::types::ProgrammableConfig;
 use anchor_lang::prelude::*;
 use solana_program::program::{invoke, invoke_signed};
 
-use crate::{sysvar_instructions_program, SharedError};
+use crate::{assert_transferable, sysvar_instructions_program, SharedError};
 
 pub mod auth_rules_program {
     use super::*;
@@ -190,6 +190,8 @@ pub fn transfer_pnft<'info>(
     payer: &AccountInfo<'info>,
     extra_accounts: &MetaplexProgrammableTransferExtraAccounts<'info>,
 ) -> Result<()> {
+    assert_transferable(mint)?;
+
     // move the token from source token account to the target token account
 
     let expected_token_account = anchor_spl::associated_token::get_associated_token_address(
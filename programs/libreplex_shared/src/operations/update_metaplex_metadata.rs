@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+
+use mpl_token_metadata::{
+    accounts::Metadata,
+    instructions::UpdateV1Builder,
+    types::{CollectionToggle, Creator, Data, RuleSetToggle},
+};
+use solana_program::program::{invoke, invoke_signed};
+
+use crate::SharedError;
+
+/// Changes to apply to a Metaplex metadata account. Anything left as None
+/// (or Toggle::None) is unchanged.
+pub struct MetaplexMetadataUpdate {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub seller_fee_basis_points: Option<u16>,
+    pub creators: Option<Vec<Creator>>,
+    /// Can only be flipped to true.
+    pub primary_sale_happened: Option<bool>,
+    pub collection: CollectionToggle,
+    /// Only applies to pNFTs.
+    pub rule_set: RuleSetToggle,
+    pub new_update_authority: Option<Pubkey>,
+    /// Can only be flipped to false, after which no further updates are
+    /// possible.
+    pub is_mutable: Option<bool>,
+}
+
+impl Default for MetaplexMetadataUpdate {
+    fn default() -> Self {
+        Self {
+            name: None,
+            symbol: None,
+            uri: None,
+            seller_fee_basis_points: None,
+            creators: None,
+            primary_sale_happened: None,
+            collection: CollectionToggle::None,
+            rule_set: RuleSetToggle::None,
+            new_update_authority: None,
+            is_mutable: None,
+        }
+    }
+}
+
+/// Updates a Metaplex metadata account via UpdateV1, signing as the update
+/// authority.
+///
+/// name, symbol, uri, seller fee and creators are merged into the current
+/// data, since UpdateV1 replaces them as a whole.
+pub fn update_metaplex_metadata<'info>(
+    metadata: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    edition: Option<&AccountInfo<'info>>,
+    update_authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    sysvar_instructions: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    update: MetaplexMetadataUpdate,
+    authority_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    let metadata_obj = Metadata::try_from(metadata)?;
+    if metadata_obj.update_authority != update_authority.key() {
+        return Err(SharedError::BadMetadataUpdateAuthority.into());
+    }
+
+    let data = if update.name.is_some()
+        || update.symbol.is_some()
+        || update.uri.is_some()
+        || update.seller_fee_basis_points.is_some()
+        || update.creators.is_some()
+    {
+        Some(Data {
+            name: update
+                .name
+                .unwrap_or(metadata_obj.name.trim_end_matches('\0').to_owned()),
+            symbol: update
+                .symbol
+                .unwrap_or(metadata_obj.symbol.trim_end_matches('\0').to_owned()),
+            uri: update
+                .uri
+                .unwrap_or(metadata_obj.uri.trim_end_matches('\0').to_owned()),
+            seller_fee_basis_points: update
+                .seller_fee_basis_points
+                .unwrap_or(metadata_obj.seller_fee_basis_points),
+            creators: update.creators.or(metadata_obj.creators),
+        })
+    } else {
+        None
+    };
+
+    let mut update_builder = UpdateV1Builder::new();
+    update_builder
+        .authority(update_authority.key())
+        .mint(mint.key())
+        .metadata(metadata.key())
+        .edition(edition.map(|x| x.key()))
+        .payer(payer.key())
+        .collection(update.collection)
+        .rule_set(update.rule_set);
+    if let Some(x) = data {
+        update_builder.data(x);
+    }
+    if let Some(x) = update.primary_sale_happened {
+        update_builder.primary_sale_happened(x);
+    }
+    if let Some(x) = update.new_update_authority {
+        update_builder.new_update_authority(x);
+    }
+    if let Some(x) = update.is_mutable {
+        update_builder.is_mutable(x);
+    }
+    let ix = update_builder.instruction();
+
+    let mut account_infos = vec![
+        update_authority.to_account_info(),
+        mint.to_account_info(),
+        metadata.to_account_info(),
+        payer.to_account_info(),
+        system_program.to_account_info(),
+        sysvar_instructions.to_account_info(),
+        metadata_program.to_account_info(),
+    ];
+    if let Some(x) = edition {
+        account_infos.push(x.to_account_info());
+    }
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, account_infos.as_slice(), &[x])?;
+        }
+        None => {
+            invoke(&ix, account_infos.as_slice())?;
+        }
+    }
+
+    Ok(())
+}
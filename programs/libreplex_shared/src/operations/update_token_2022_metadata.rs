@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use solana_program::{
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
+use spl_token_2022::{
+    extension::{metadata_pointer::MetadataPointer, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+use spl_token_metadata_interface::{
+    instruction::{remove_key, update_field},
+    state::{Field, TokenMetadata},
+};
+
+use crate::SharedError;
+
+/// Updates the metadata embedded in a token-2022 mint.
+///
+/// Field updates are applied in order, followed by the key removals. If the
+/// metadata grows, payer tops up the mint so that it stays rent exempt.
+pub fn update_token_2022_metadata<'info>(
+    mint: &AccountInfo<'info>,
+    update_authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    field_updates: Vec<(Field, String)>,
+    remove_keys: Vec<String>,
+    authority_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    if !mint.owner.eq(&spl_token_2022::ID) {
+        return Err(SharedError::UnexpectedTokenType.into());
+    }
+
+    let new_account_len = {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_obj = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+
+        // only the native implementation (metadata stored on the mint) is
+        // supported
+        let metadata_pointer = mint_obj
+            .get_extension::<MetadataPointer>()
+            .map_err(|_| SharedError::MissingMetadata)?;
+        if Option::<Pubkey>::from(metadata_pointer.metadata_address) != Some(mint.key()) {
+            return Err(SharedError::MissingMetadata.into());
+        }
+
+        let mut metadata = mint_obj.get_variable_len_extension::<TokenMetadata>()?;
+        if Option::<Pubkey>::from(metadata.update_authority) != Some(update_authority.key()) {
+            return Err(SharedError::BadMetadataUpdateAuthority.into());
+        }
+
+        for (field, value) in field_updates.iter() {
+            metadata.update(field.clone(), value.clone());
+        }
+        for key in remove_keys.iter() {
+            metadata.remove_key(key);
+        }
+
+        mint_obj.try_get_new_account_len_for_variable_len_extension(&metadata)?
+    };
+
+    let required_lamports = Rent::get()?.minimum_balance(new_account_len);
+    if required_lamports > mint.lamports() {
+        msg!("Top up mint rent");
+        invoke(
+            &system_instruction::transfer(
+                &payer.key(),
+                &mint.key(),
+                required_lamports - mint.lamports(),
+            ),
+            &[
+                payer.to_account_info(),
+                mint.to_account_info(),
+                system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    let account_infos = &[mint.to_account_info(), update_authority.to_account_info()];
+
+    for (field, value) in field_updates {
+        let update_field_ix = update_field(
+            &spl_token_2022::ID,
+            &mint.key(),
+            &update_authority.key(),
+            field,
+            value,
+        );
+        match authority_seeds {
+            Some(x) => {
+                invoke_signed(&update_field_ix, account_infos, &[x])?;
+            }
+            None => {
+                invoke(&update_field_ix, account_infos)?;
+            }
+        }
+    }
+
+    for key in remove_keys {
+        let remove_key_ix = remove_key(
+            &spl_token_2022::ID,
+            &mint.key(),
+            &update_authority.key(),
+            key,
+            true,
+        );
+        match authority_seeds {
+            Some(x) => {
+                invoke_signed(&remove_key_ix, account_infos, &[x])?;
+            }
+            None => {
+                invoke(&remove_key_ix, account_infos)?;
+            }
+        }
+    }
+
+    Ok(())
+}
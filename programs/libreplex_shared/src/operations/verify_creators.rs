@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use mpl_token_metadata::{
+    accounts::Metadata,
+    instructions::{UnverifyCreatorV1Builder, VerifyCreatorV1Builder},
+};
+use solana_program::program::{invoke, invoke_signed};
+
+use crate::SharedError;
+
+/// Verifies every given creator on a Metaplex metadata account via VerifyV1,
+/// or removes their verification via UnverifyV1 if verify is false.
+///
+/// Each creator must be a signer of the transaction or a PDA that signs via
+/// authority_seeds, and must appear in the creators list of the metadata.
+///
+/// Unlike the single signer of update_metaplex_metadata and friends,
+/// authority_seeds holds one seed set (including the bump) per PDA creator,
+/// as several creators can be PDAs. All of them are passed to every
+/// VerifyV1/UnverifyV1 call.
+pub fn verify_creators<'info>(
+    metadata: &AccountInfo<'info>,
+    creators: &[&AccountInfo<'info>],
+    system_program: &AccountInfo<'info>,
+    sysvar_instructions: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    verify: bool,
+    authority_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    let metadata_obj = Metadata::try_from(metadata)?;
+    let metadata_creators = metadata_obj.creators.unwrap_or_default();
+
+    for creator in creators {
+        if !metadata_creators.iter().any(|x| x.address == creator.key()) {
+            return Err(SharedError::CreatorNotFound.into());
+        }
+
+        let ix = if verify {
+            VerifyCreatorV1Builder::new()
+                .authority(creator.key())
+                .metadata(metadata.key())
+                .instruction()
+        } else {
+            UnverifyCreatorV1Builder::new()
+                .authority(creator.key())
+                .metadata(metadata.key())
+                .instruction()
+        };
+
+        let account_infos = &[
+            creator.to_account_info(),
+            metadata.to_account_info(),
+            system_program.to_account_info(),
+            sysvar_instructions.to_account_info(),
+            metadata_program.to_account_info(),
+        ];
+
+        match authority_seeds {
+            Some(x) => {
+                invoke_signed(&ix, account_infos, x)?;
+            }
+            None => {
+                invoke(&ix, account_infos)?;
+            }
+        }
+    }
+
+    Ok(())
+}
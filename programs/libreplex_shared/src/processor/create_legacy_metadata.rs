@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 use mpl_token_metadata::{
     instructions::CreateV1CpiBuilder,
-    types::{Creator, TokenStandard},
+    types::{Creator, PrintSupply, TokenStandard},
 };
 use solana_program::account_info::AccountInfo;
+use spl_token_2022::{extension::StateWithExtensions, state::Mint};
+
+use crate::{verify_collection, CollectionAccounts, SharedError};
 
 /// Accounts to mint an NFT.
 pub struct CreateMetadataAccounts<'info> {
@@ -12,6 +15,7 @@ pub struct CreateMetadataAccounts<'info> {
     pub nft_mint: AccountInfo<'info>,
     pub nft_mint_authority: AccountInfo<'info>,
     pub nft_metadata: AccountInfo<'info>,
+    /// Only used for NonFungible and ProgrammableNonFungible.
     pub nft_master_edition: AccountInfo<'info>,
     pub system_program: AccountInfo<'info>,
     pub rent: AccountInfo<'info>,
@@ -22,8 +26,16 @@ pub struct CreateMetadataAccounts<'info> {
 }
 
 /// Creates the metadata accounts and mint a new token.
-pub fn create_legacy_metadata(
-    accounts: CreateMetadataAccounts,
+///
+/// token_standard can be Fungible, FungibleAsset, NonFungible or
+/// ProgrammableNonFungible. Everything except Fungible needs a mint with
+/// 0 decimals. NonFungibles also get a master edition, with print_supply
+/// defaulting to PrintSupply::Zero.
+///
+/// If collection is given, it is set on the metadata and verified with
+/// authority_seeds.
+pub fn create_legacy_metadata<'info>(
+    accounts: CreateMetadataAccounts<'info>,
     authority_seeds: &[&[u8]],
     name: String,
     symbol: String,
@@ -32,9 +44,31 @@ pub fn create_legacy_metadata(
     creators: Vec<Creator>,
     // new_update_auth: Pubkey,
     is_mutable: bool,
+    token_standard: TokenStandard,
+    primary_sale_happened: bool,
+    collection: Option<CollectionAccounts<'info>>,
+    print_supply: Option<PrintSupply>,
 ) -> Result<()> {
+    let decimals = {
+        let mint_data = accounts.nft_mint.try_borrow_data()?;
+        StateWithExtensions::<Mint>::unpack(&mint_data)?.base.decimals
+    };
+
+    let is_non_fungible = match token_standard {
+        TokenStandard::Fungible => false,
+        TokenStandard::FungibleAsset => false,
+        TokenStandard::NonFungible => true,
+        TokenStandard::ProgrammableNonFungible => true,
+        _ => {
+            return Err(SharedError::UnexpectedTokenType.into());
+        }
+    };
+    if token_standard != TokenStandard::Fungible && decimals != 0 {
+        return Err(SharedError::BadMintDecimals.into());
+    }
+
     let mut create_metadata_ix_builder = CreateV1CpiBuilder::new(&accounts.metadata_program);
-    
+
 
     create_metadata_ix_builder
     .metadata(&accounts.nft_metadata)
@@ -49,9 +83,33 @@ pub fn create_legacy_metadata(
     .uri(uri)
     .seller_fee_basis_points(seller_fee_basis_points)
     .creators(creators)
-    .primary_sale_happened(true)
+    .primary_sale_happened(primary_sale_happened)
     .is_mutable(is_mutable)
-    .token_standard(TokenStandard::Fungible).invoke_signed(&[&authority_seeds])?;
+    .token_standard(token_standard);
+
+    if is_non_fungible {
+        create_metadata_ix_builder
+            .master_edition(Some(&accounts.nft_master_edition))
+            .print_supply(print_supply.unwrap_or(PrintSupply::Zero));
+    }
+
+    if let Some(x) = &collection {
+        create_metadata_ix_builder.collection(x.to_collection());
+    }
+
+    create_metadata_ix_builder.invoke_signed(&[authority_seeds])?;
+
+    if let Some(x) = &collection {
+        verify_collection(
+            &accounts.nft_metadata,
+            x,
+            &accounts.payer,
+            &accounts.metadata_program,
+            &accounts.system_program,
+            Some(&accounts.sysvar_instructions_info),
+            Some(authority_seeds),
+        )?;
+    }
 
     Ok(())
 }
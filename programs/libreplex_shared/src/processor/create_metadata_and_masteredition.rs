@@ -11,7 +11,7 @@ use solana_program::program::{invoke_signed, invoke};
 
 use anchor_lang::prelude::*;
 
-use crate::SharedError;
+use crate::{verify_collection, CollectionAccounts, SharedError};
 
 pub fn create_metadata_and_masteredition<'f>(
     payer: &AccountInfo<'f>,
@@ -32,6 +32,7 @@ pub fn create_metadata_and_masteredition<'f>(
     max_supply: Option<u64>,
     authority_seeds: Option<&[&[u8]]>,
     is_spl: bool,
+    collection: Option<CollectionAccounts<'f>>,
 ) -> Result<()> {
     let payer_key = payer.key();
     let mint_key = mint.key();
@@ -53,9 +54,30 @@ pub fn create_metadata_and_masteredition<'f>(
             uri: offchain_url.clone(),
             seller_fee_basis_points: royalties_basis_points,
             creators: creators.to_owned(),
-            collection: None,
+            collection: collection.as_ref().map(|x| x.to_collection()),
             uses: None,
-        });
+        })
+        .is_mutable(true);
+
+    let create_metadata_infos = vec![
+        metadata.to_account_info(),
+        mint.to_account_info(),
+        owner.to_account_info(),
+        payer.to_account_info(),
+        system_program.to_account_info(),
+        metadata_program.to_account_info(),
+    ];
+
+    let ix = create_metadata_builder.instruction();
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, create_metadata_infos.as_slice(), &[x])?;
+        }
+        None => {
+            invoke(&ix, create_metadata_infos.as_slice())?;
+        }
+    }
 
     // only create master edition and verify creators if decimals is 0 (i.e. we
     // have an NFT)
@@ -131,5 +153,17 @@ pub fn create_metadata_and_masteredition<'f>(
         }
     }
 
+    if let Some(x) = &collection {
+        verify_collection(
+            metadata,
+            x,
+            payer,
+            metadata_program,
+            system_program,
+            None,
+            authority_seeds,
+        )?;
+    }
+
     Ok(())
 }
@@ -13,7 +13,7 @@ use mpl_token_metadata::{
 };
 use solana_program::program::invoke_signed;
 
-use crate::SharedError;
+use crate::{verify_collection, CollectionAccounts, SharedError};
 
 /// Accounts to mint an NFT.
 pub struct MintAccounts<'info> {
@@ -34,8 +34,11 @@ pub struct MintAccounts<'info> {
 }
 
 /// Creates the metadata accounts and mint a new token.
-pub fn create_mint_with_metadata_and_masteredition(
-    accounts: MintAccounts,
+///
+/// mint_seeds are only needed when nft_mint is a PDA (see find_mint_address)
+/// and sign CreateV1 next to authority_seeds.
+pub fn create_mint_with_metadata_and_masteredition<'info>(
+    accounts: MintAccounts<'info>,
     authority_seeds: &[&[u8]],
     name: String,
     symbol: String,
@@ -48,6 +51,8 @@ pub fn create_mint_with_metadata_and_masteredition(
     mint_amount: u64,
     decimals: u8,
     token_standard: TokenStandard,
+    collection: Option<CollectionAccounts<'info>>,
+    mint_seeds: Option<&[&[u8]]>,
 ) -> Result<()> {
     let sysvar_instructions_info = accounts.sysvar_instructions;
 
@@ -57,7 +62,10 @@ pub fn create_mint_with_metadata_and_masteredition(
     let mut create_ix_builder = CreateV1Builder::new();
     create_ix_builder
         .metadata(accounts.nft_metadata.key())
-        .mint(accounts.nft_mint.key(), accounts.nft_mint.is_signer)
+        .mint(
+            accounts.nft_mint.key(),
+            accounts.nft_mint.is_signer || mint_seeds.is_some(),
+        )
         .authority(accounts.nft_mint_authority.key())
         .payer(accounts.payer.key())
         .update_authority(accounts.authority_pda.key(), true)
@@ -77,6 +85,9 @@ pub fn create_mint_with_metadata_and_masteredition(
     if let Some(x) = &creators {
         create_ix_builder.creators(x.to_vec());
     };
+    if let Some(x) = &collection {
+        create_ix_builder.collection(x.to_collection());
+    };
     let create_ix = create_ix_builder.instruction();
 
     let mut create_infos = vec![
@@ -93,7 +104,12 @@ pub fn create_mint_with_metadata_and_masteredition(
     create_infos.push(sysvar_instructions_info.to_account_info());
     create_infos.push(accounts.spl_token_program.to_account_info());
 
-    invoke_signed(&create_ix, &create_infos, &[&authority_seeds])?;
+    let create_signer_seeds = match mint_seeds {
+        Some(x) => vec![authority_seeds, x],
+        None => vec![authority_seeds],
+    };
+
+    invoke_signed(&create_ix, &create_infos, &create_signer_seeds)?;
 
     // mints one token
 
@@ -142,5 +158,17 @@ pub fn create_mint_with_metadata_and_masteredition(
         invoke_signed(&mint_ix, &mint_infos, &[&authority_seeds])?;
     }
 
+    if let Some(x) = &collection {
+        verify_collection(
+            &accounts.nft_metadata,
+            x,
+            &accounts.payer,
+            &accounts.token_metadata_program,
+            &accounts.system_program,
+            Some(&sysvar_instructions_info),
+            Some(authority_seeds),
+        )?;
+    }
+
     Ok(())
 }
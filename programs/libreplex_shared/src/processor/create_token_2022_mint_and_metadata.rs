@@ -8,16 +8,26 @@ use solana_program::{
 use spl_token_2022::{
     extension::{group_pointer::GroupPointer, transfer_fee::TransferFeeConfig, ExtensionType},
     instruction::initialize_mint2,
-    state::Mint,
+    state::{AccountState, Mint},
 };
 
 use spl_token_group_interface::{
     instruction::{initialize_group, initialize_member},
     state::{TokenGroup, TokenGroupMember},
 };
-use spl_token_metadata_interface::{instruction::initialize, state::TokenMetadata};
+use spl_token_metadata_interface::{
+    instruction::{initialize, update_authority, update_field},
+    state::{Field, TokenMetadata},
+};
+use spl_pod::optional_keys::OptionalNonZeroPubkey;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address, instruction::initialize_extra_account_meta_list,
+};
 use spl_type_length_value::state::{TlvState, TlvStateBorrowed};
 
+use crate::SharedError;
+
 /// Accounts to mint an NFT.
 pub struct MintAccounts2022<'info> {
     pub authority: AccountInfo<'info>,
@@ -42,17 +52,69 @@ pub struct TransferFeeParams {
     pub withdraw_fee_authority: Pubkey,
 }
 
+pub struct TransferHookValidationInput<'f> {
+    pub hook_program: AccountInfo<'f>,
+    pub extra_account_metas_account: AccountInfo<'f>,
+    pub system_program: AccountInfo<'f>,
+    pub extra_account_metas: Vec<ExtraAccountMeta>,
+}
+
+pub struct TransferHookParams<'f> {
+    pub program_id: Pubkey,
+    pub authority: Option<Pubkey>,
+    /// If specified, the ExtraAccountMetaList validation account of the hook
+    /// is created and populated for the new mint
+    pub validation: Option<TransferHookValidationInput<'f>>,
+}
+
+pub struct InterestBearingParams {
+    pub rate_authority: Option<Pubkey>,
+    /// Interest rate in basis points
+    pub rate: i16,
+}
+
+/// Additional mint extensions. Everything is off by default.
+#[derive(Default)]
+pub struct MintExtensionParams<'f> {
+    /// Enables MintCloseAuthority so the mint can be closed and its rent
+    /// reclaimed once the supply drops back to zero.
+    pub close_authority: Option<Pubkey>,
+    /// Enables NonTransferable, making every token of this mint soulbound.
+    pub non_transferable: bool,
+    /// Enables PermanentDelegate. The delegate can transfer or burn tokens
+    /// from any holder (see clawback).
+    pub permanent_delegate: Option<Pubkey>,
+    /// Enables TransferHook, calling into the hook program on every transfer.
+    pub transfer_hook: Option<TransferHookParams<'f>>,
+    /// Enables InterestBearingConfig. Only affects the UI amount, balances
+    /// are never changed.
+    pub interest_bearing: Option<InterestBearingParams>,
+    /// Enables DefaultAccountState. Use AccountState::Frozen for mints where
+    /// every new token account has to be thawed by the freeze authority.
+    pub default_account_state: Option<AccountState>,
+}
+
 /// Creates the metadata accounts and mint a new token.
+///
+/// The mint either signs the transaction or is a PDA of the calling program,
+/// in which case mint_seeds (including the bump) sign for it.
+///
+/// The update authority of token_metadata is ignored. The metadata is
+/// updatable by authority unless metadata_update_authority is given, in which
+/// case it is handed over to that key or, with Some(None), made immutable.
 pub fn create_token_2022_and_metadata<'a>(
     accounts: MintAccounts2022<'a>,
     decimals: u8,
     token_metadata: Option<TokenMetadata>,
+    metadata_update_authority: Option<Option<Pubkey>>,
     // token group is optional - specifying this turns this into a group mint
     token_group: Option<TokenGroupInput<'a>>,
     token_member: Option<TokenMemberInput<'a>>,
     auth_seeds: Option<&[&[u8]]>,
     transfer_fee_params: Option<TransferFeeParams>,
     token_group_program_id: Option<Pubkey>,
+    extension_params: MintExtensionParams<'a>,
+    mint_seeds: Option<&[&[u8]]>,
 ) -> Result<()> {
     // msg!("create_token_2022_and_metadata called");
     let MintAccounts2022 {
@@ -73,7 +135,13 @@ pub fn create_token_2022_and_metadata<'a>(
     match &token_metadata {
         Some(x) => {
             extension_types.push(ExtensionType::MetadataPointer);
-            extension_extra_space += x.tlv_size_of().unwrap();
+            // size for the final metadata, including the additional fields
+            // written after initialisation
+            let final_metadata = TokenMetadata {
+                mint: nft_mint.key(),
+                ..x.clone()
+            };
+            extension_extra_space += final_metadata.tlv_size_of()?;
         }
         None => {}
     };
@@ -99,6 +167,30 @@ pub fn create_token_2022_and_metadata<'a>(
         }
     }
 
+    if extension_params.close_authority.is_some() {
+        extension_types.push(ExtensionType::MintCloseAuthority);
+    }
+
+    if extension_params.non_transferable {
+        extension_types.push(ExtensionType::NonTransferable);
+    }
+
+    if extension_params.permanent_delegate.is_some() {
+        extension_types.push(ExtensionType::PermanentDelegate);
+    }
+
+    if extension_params.transfer_hook.is_some() {
+        extension_types.push(ExtensionType::TransferHook);
+    }
+
+    if extension_params.interest_bearing.is_some() {
+        extension_types.push(ExtensionType::InterestBearingConfig);
+    }
+
+    if extension_params.default_account_state.is_some() {
+        extension_types.push(ExtensionType::DefaultAccountState);
+    }
+
     match &token_member {
         Some(_) => {
             extension_types.push(ExtensionType::GroupMemberPointer);
@@ -122,14 +214,20 @@ pub fn create_token_2022_and_metadata<'a>(
 
     msg!("Invoke create account {},{}", payer.key(), nft_mint.key());
 
-    invoke(
-        &create_account_ix,
-        &[
-            nft_mint.to_account_info(),
-            payer.to_account_info(),
-            spl_token_program.to_account_info(),
-        ],
-    )?;
+    let create_account_infos = &[
+        nft_mint.to_account_info(),
+        payer.to_account_info(),
+        spl_token_program.to_account_info(),
+    ];
+
+    match mint_seeds {
+        Some(x) => {
+            invoke_signed(&create_account_ix, create_account_infos, &[x])?;
+        }
+        None => {
+            invoke(&create_account_ix, create_account_infos)?;
+        }
+    }
 
     if token_metadata.is_some() {
         let initialize_extension =
@@ -272,6 +370,79 @@ pub fn create_token_2022_and_metadata<'a>(
         }
         None => {}
     }
+    if let Some(close_authority) = &extension_params.close_authority {
+        let initialize_close_authority_ix =
+            spl_token_2022::instruction::initialize_mint_close_authority(
+                &spl_token_2022::ID,
+                &nft_mint.key(),
+                Some(close_authority),
+            )?;
+
+        msg!("Invoke initialise mint close authority");
+        invoke(&initialize_close_authority_ix, &[nft_mint.to_account_info()])?;
+    }
+
+    if extension_params.non_transferable {
+        let initialize_non_transferable_ix =
+            spl_token_2022::instruction::initialize_non_transferable_mint(
+                &spl_token_2022::ID,
+                &nft_mint.key(),
+            )?;
+
+        msg!("Invoke initialise non-transferable");
+        invoke(&initialize_non_transferable_ix, &[nft_mint.to_account_info()])?;
+    }
+
+    if let Some(permanent_delegate) = &extension_params.permanent_delegate {
+        let initialize_permanent_delegate_ix =
+            spl_token_2022::instruction::initialize_permanent_delegate(
+                &spl_token_2022::ID,
+                &nft_mint.key(),
+                permanent_delegate,
+            )?;
+
+        msg!("Invoke initialise permanent delegate");
+        invoke(&initialize_permanent_delegate_ix, &[nft_mint.to_account_info()])?;
+    }
+
+    if let Some(transfer_hook) = &extension_params.transfer_hook {
+        let initialize_transfer_hook_ix =
+            spl_token_2022::extension::transfer_hook::instruction::initialize(
+                &spl_token_2022::ID,
+                &nft_mint.key(),
+                transfer_hook.authority,
+                Some(transfer_hook.program_id),
+            )?;
+
+        msg!("Invoke initialise transfer hook");
+        invoke(&initialize_transfer_hook_ix, &[nft_mint.to_account_info()])?;
+    }
+
+    if let Some(interest_bearing) = &extension_params.interest_bearing {
+        let initialize_interest_bearing_ix =
+            spl_token_2022::extension::interest_bearing_mint::instruction::initialize(
+                &spl_token_2022::ID,
+                &nft_mint.key(),
+                interest_bearing.rate_authority,
+                interest_bearing.rate,
+            )?;
+
+        msg!("Invoke initialise interest bearing config");
+        invoke(&initialize_interest_bearing_ix, &[nft_mint.to_account_info()])?;
+    }
+
+    if let Some(default_account_state) = &extension_params.default_account_state {
+        let initialize_default_account_state_ix =
+            spl_token_2022::extension::default_account_state::instruction::initialize_default_account_state(
+                &spl_token_2022::ID,
+                &nft_mint.key(),
+                default_account_state,
+            )?;
+
+        msg!("Invoke initialise default account state");
+        invoke(&initialize_default_account_state_ix, &[nft_mint.to_account_info()])?;
+    }
+
     msg!("Invoke initialise mint");
 
     let initialize_ix = initialize_mint2(
@@ -315,6 +486,110 @@ pub fn create_token_2022_and_metadata<'a>(
                 invoke(&initialise_metadata_ix, account_infos)?;
             }
         }
+
+        let update_account_infos = &[nft_mint.to_account_info(), authority.to_account_info()];
+
+        // initialize only takes name, symbol and uri. everything else is
+        // written as a separate update while we are still the update authority
+        for (key, value) in x.additional_metadata.iter() {
+            let update_field_ix = update_field(
+                &spl_token_2022::ID,
+                &nft_mint.key(),
+                &authority.key(),
+                Field::Key(key.clone()),
+                value.clone(),
+            );
+            match auth_seeds {
+                Some(y) => {
+                    invoke_signed(&update_field_ix, update_account_infos, &[y])?;
+                }
+                None => {
+                    invoke(&update_field_ix, update_account_infos)?;
+                }
+            }
+        }
+
+        // hand over to the requested update authority. Some(None) makes the
+        // metadata immutable
+        if let Some(new_update_authority) =
+            metadata_update_authority.filter(|y| *y != Some(authority.key()))
+        {
+            msg!("Set metadata update authority");
+            let update_authority_ix = update_authority(
+                &spl_token_2022::ID,
+                &nft_mint.key(),
+                &authority.key(),
+                OptionalNonZeroPubkey::try_from(new_update_authority)?,
+            );
+            match auth_seeds {
+                Some(y) => {
+                    invoke_signed(&update_authority_ix, update_account_infos, &[y])?;
+                }
+                None => {
+                    invoke(&update_authority_ix, update_account_infos)?;
+                }
+            }
+        }
+    }
+
+    if let Some(TransferHookParams {
+        program_id,
+        validation: Some(validation),
+        ..
+    }) = &extension_params.transfer_hook
+    {
+        let expected_extra_account_metas_address =
+            get_extra_account_metas_address(&nft_mint.key(), program_id);
+        if expected_extra_account_metas_address != validation.extra_account_metas_account.key() {
+            return Err(SharedError::DerivedKeyInvalid.into());
+        }
+
+        // the hook program allocates and assigns the account itself, but
+        // expects it to be funded already
+        let space = ExtraAccountMetaList::size_of(validation.extra_account_metas.len())?;
+        let required_lamports = rent
+            .minimum_balance(space)
+            .saturating_sub(validation.extra_account_metas_account.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    &payer.key(),
+                    &validation.extra_account_metas_account.key(),
+                    required_lamports,
+                ),
+                &[
+                    payer.to_account_info(),
+                    validation.extra_account_metas_account.to_account_info(),
+                    validation.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        let initialize_extra_account_metas_ix = initialize_extra_account_meta_list(
+            program_id,
+            &validation.extra_account_metas_account.key(),
+            &nft_mint.key(),
+            &authority.key(),
+            &validation.extra_account_metas,
+        );
+
+        let account_infos = &[
+            validation.extra_account_metas_account.to_account_info(),
+            nft_mint.to_account_info(),
+            authority.to_account_info(),
+            validation.system_program.to_account_info(),
+            validation.hook_program.to_account_info(),
+        ];
+
+        msg!("Initialise extra account metas");
+        match auth_seeds {
+            Some(y) => {
+                invoke_signed(&initialize_extra_account_metas_ix, account_infos, &[y])?;
+            }
+            None => {
+                invoke(&initialize_extra_account_metas_ix, account_infos)?;
+            }
+        }
     }
 
     if let Some(program_id) = token_group_program_id {
@@ -5,8 +5,7 @@ use mpl_token_metadata::instructions::{
 use mpl_token_metadata::instructions::SignMetadataBuilder;
 
 
-use anchor_spl::token::Mint as SplMint;
-use anchor_spl::token::ID;
+use spl_token_2022::{extension::ExtensionType, state::Mint};
 
 use mpl_token_metadata::types::Creator;
 use mpl_token_metadata::types::DataV2;
@@ -19,8 +18,16 @@ use solana_program::{
 use anchor_lang::prelude::*;
 
 
-use crate::SharedError;
+use crate::{verify_collection, CollectionAccounts, SharedError};
 
+/// Creates a mint with Metaplex metadata (and a master edition for NFTs).
+///
+/// The mint is created under token_program, which can be either spl-token or
+/// token-2022. mint_close_authority enables the MintCloseAuthority extension
+/// and is only allowed for token-2022.
+///
+/// For mints at program derived addresses, mint_seeds sign the creation of
+/// the mint account together with authority_seeds.
 pub fn create_mint_with_metadata_and_masteredition<'f>(
     payer: &AccountInfo<'f>,
     owner: &AccountInfo<'f>,
@@ -46,6 +53,9 @@ pub fn create_mint_with_metadata_and_masteredition<'f>(
     max_supply: Option<u64>,
     authority_seeds: &[&[u8]],
     is_spl: bool,
+    collection: Option<CollectionAccounts<'f>>,
+    mint_close_authority: Option<Pubkey>,
+    mint_seeds: Option<&[&[u8]]>,
 ) -> Result<()> {
     let payer_key = payer.key();
     let mint_key = mint.key();
@@ -54,26 +64,53 @@ pub fn create_mint_with_metadata_and_masteredition<'f>(
     let mint_infos = vec![payer.to_account_info(), mint.to_account_info()];
 
     let owner_key = &owner.key();
+    let token_program_key = token_program.key();
+
+    let mut extension_types = vec![];
+    if token_program_key == spl_token_2022::ID {
+        if mint_close_authority.is_some() {
+            extension_types.push(ExtensionType::MintCloseAuthority);
+        }
+    } else if token_program_key != spl_token::ID || mint_close_authority.is_some() {
+        return Err(SharedError::UnexpectedTokenType.into());
+    }
+    let mint_size = ExtensionType::try_calculate_account_len::<Mint>(&extension_types)?;
+
+    let create_mint_signer_seeds = match mint_seeds {
+        Some(x) => vec![authority_seeds, x],
+        None => vec![authority_seeds],
+    };
 
-    
     // CREATE MINT
     invoke_signed(
         &system_instruction::create_account(
             &payer_key,
             &mint_key,
             // rent.minimum_balance(Mint::LEN),
-            Rent::get()?.minimum_balance(SplMint::LEN),
-            SplMint::LEN as u64,
-            &ID,
+            Rent::get()?.minimum_balance(mint_size),
+            mint_size as u64,
+            &token_program_key,
         ),
         mint_infos.as_slice(),
-        &[&authority_seeds],
+        &create_mint_signer_seeds,
     )?;
 
+    // extensions have to be initialised before the mint itself
+    if let Some(x) = &mint_close_authority {
+        invoke(
+            &spl_token_2022::instruction::initialize_mint_close_authority(
+                &token_program_key,
+                &mint_key,
+                Some(x),
+            )?,
+            &[token_program.to_account_info(), mint.to_account_info()],
+        )?;
+    }
+
     // initialize mint
     invoke(
-        &spl_token::instruction::initialize_mint2(
-            &spl_token::id(),
+        &spl_token_2022::instruction::initialize_mint2(
+            &token_program_key,
             &mint_key,
             owner_key,
             Some(owner_key),
@@ -114,8 +151,8 @@ pub fn create_mint_with_metadata_and_masteredition<'f>(
                         // msg!("owner: {}", owner_key);
                         // mint to
                         invoke_signed(
-                            &spl_token::instruction::mint_to(
-                                &spl_token::id(),
+                            &spl_token_2022::instruction::mint_to(
+                                &token_program_key,
                                 &mint_key,
                                 token_account_key,
                                 owner_key,
@@ -151,9 +188,25 @@ pub fn create_mint_with_metadata_and_masteredition<'f>(
             uri: offchain_url.to_string(),
             seller_fee_basis_points: royalties_basis_points,
             creators: creators.to_owned(),
-            collection: None,
+            collection: collection.as_ref().map(|x| x.to_collection()),
             uses: None,
-        });
+        })
+        .is_mutable(true);
+
+    let create_metadata_infos = vec![
+        metadata.to_account_info(),
+        mint.to_account_info(),
+        owner.to_account_info(),
+        payer.to_account_info(),
+        system_program.to_account_info(),
+        metadata_program.to_account_info(),
+    ];
+
+    invoke_signed(
+        &create_metadata_builder.instruction(),
+        create_metadata_infos.as_slice(),
+        &[authority_seeds],
+    )?;
 
     // only create master edition and verify creators if decimals is 0 (i.e. we
     // have an NFT)
@@ -181,6 +234,7 @@ pub fn create_mint_with_metadata_and_masteredition<'f>(
                     .mint(mint.key())
                     .mint_authority(owner.key())
                     .update_authority(owner.key())
+                    .token_program(token_program_key)
                     .payer(payer.key())
                     .max_supply(match max_supply {
                         Some(x) => x,
@@ -218,5 +272,17 @@ pub fn create_mint_with_metadata_and_masteredition<'f>(
         }
     }
 
+    if let Some(x) = &collection {
+        verify_collection(
+            metadata,
+            x,
+            payer,
+            metadata_program,
+            system_program,
+            None,
+            Some(authority_seeds),
+        )?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::assert_derivation;
+
+/// Seed prefix of mints created at program derived addresses.
+pub const MINT_SEED: &[u8] = b"mint";
+
+/// Derives the mint address for the given deployment and index. Works both
+/// on-chain and off-chain.
+///
+/// To sign for the mint, pass [MINT_SEED, deployment, index (little endian),
+/// bump] as mint_seeds.
+pub fn find_mint_address(program_id: &Pubkey, deployment: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MINT_SEED, deployment.as_ref(), &index.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Checks that mint is the mint address for the given deployment and index
+/// and returns its bump.
+pub fn assert_mint_address(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    deployment: &Pubkey,
+    index: u64,
+) -> Result<u8> {
+    assert_derivation(
+        program_id,
+        mint,
+        &[MINT_SEED, deployment.as_ref(), &index.to_le_bytes()],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mint_address_round_trip() {
+        let program_id = Pubkey::new_unique();
+        let deployment = Pubkey::new_unique();
+        let (mint, bump) = find_mint_address(&program_id, &deployment, 7);
+
+        assert_eq!(
+            assert_mint_address(&program_id, &mint, &deployment, 7).unwrap(),
+            bump
+        );
+        // the documented mint_seeds sign for the mint
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[MINT_SEED, deployment.as_ref(), &7u64.to_le_bytes(), &[bump]],
+                &program_id
+            )
+            .unwrap(),
+            mint
+        );
+    }
+
+    #[test]
+    fn mint_address_rejects_wrong_index() {
+        let program_id = Pubkey::new_unique();
+        let deployment = Pubkey::new_unique();
+        let (mint, _) = find_mint_address(&program_id, &deployment, 7);
+
+        assert!(assert_mint_address(&program_id, &mint, &deployment, 8).is_err());
+    }
+}
@@ -21,4 +21,10 @@ pub use create_metadata_and_masteredition::*;
 
 pub mod create_mint_metadata_and_masteredition;
 
-pub use create_mint_metadata_and_masteredition::*;
\ No newline at end of file
+pub use create_mint_metadata_and_masteredition::*;
+
+pub mod verify_collection;
+pub use verify_collection::*;
+
+pub mod mint_address;
+pub use mint_address::*;
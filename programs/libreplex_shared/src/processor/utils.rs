@@ -2,6 +2,10 @@
 use std::collections::BTreeMap;
 
 use anchor_lang::prelude::*;
+use spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::Mint,
+};
 
 use crate::SharedError;
 
@@ -25,3 +29,19 @@ pub fn check_bump(bump_name: &String, bumps: &BTreeMap<String, u8>, bump: u8) ->
     Ok(())
 }
 
+/// Fails with NonTransferableMint if the mint is a token-2022 mint with the
+/// NonTransferable extension. Legacy mints are always transferable.
+pub fn assert_transferable(mint: &AccountInfo) -> Result<()> {
+    if !mint.owner.eq(&spl_token_2022::ID) {
+        return Ok(());
+    }
+    let mint_data = mint.try_borrow_data()?;
+    let mint_obj = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    if mint_obj
+        .get_extension_types()?
+        .contains(&ExtensionType::NonTransferable)
+    {
+        return Err(SharedError::NonTransferableMint.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use mpl_token_metadata::{
+    accounts::Metadata,
+    instructions::{
+        VerifyCollectionBuilder, VerifyCollectionV1Builder, VerifySizedCollectionItemBuilder,
+    },
+    types::Collection,
+};
+use solana_program::program::{invoke, invoke_signed};
+
+/// Accounts of the collection parent that a new NFT is grouped under.
+pub struct CollectionAccounts<'info> {
+    pub collection_mint: AccountInfo<'info>,
+    pub collection_metadata: AccountInfo<'info>,
+    pub collection_master_edition: AccountInfo<'info>,
+    /// Update authority of the collection, or the holder of delegate_record.
+    pub collection_authority: AccountInfo<'info>,
+    /// Collection delegate record (VerifyCollectionV1) or collection authority
+    /// record (legacy verification). Only needed when collection_authority is
+    /// not the update authority of the collection.
+    pub delegate_record: Option<AccountInfo<'info>>,
+}
+
+impl<'info> CollectionAccounts<'info> {
+    /// Unverified collection entry to set on the metadata at creation time.
+    pub fn to_collection(&self) -> Collection {
+        Collection {
+            verified: false,
+            key: self.collection_mint.key(),
+        }
+    }
+}
+
+/// Verifies metadata as a member of a collection.
+///
+/// With sysvar_instructions, VerifyCollectionV1 is used (required for pNFTs).
+/// Otherwise the legacy instructions are used, picking sized collection
+/// verification if the collection metadata has collection details.
+///
+/// authority_seeds sign for the collection authority if it is a PDA.
+pub fn verify_collection<'info>(
+    metadata: &AccountInfo<'info>,
+    collection: &CollectionAccounts<'info>,
+    payer: &AccountInfo<'info>,
+    metadata_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    sysvar_instructions: Option<&AccountInfo<'info>>,
+    authority_seeds: Option<&[&[u8]]>,
+) -> Result<()> {
+    let delegate_record_key = collection.delegate_record.as_ref().map(|x| x.key());
+
+    let mut verify_infos = vec![
+        collection.collection_authority.to_account_info(),
+        metadata.to_account_info(),
+        payer.to_account_info(),
+        collection.collection_mint.to_account_info(),
+        collection.collection_metadata.to_account_info(),
+        collection.collection_master_edition.to_account_info(),
+        metadata_program.to_account_info(),
+    ];
+    if let Some(x) = &collection.delegate_record {
+        verify_infos.push(x.to_account_info());
+    }
+
+    let ix = match sysvar_instructions {
+        Some(x) => {
+            verify_infos.push(system_program.to_account_info());
+            verify_infos.push(x.to_account_info());
+            VerifyCollectionV1Builder::new()
+                .authority(collection.collection_authority.key())
+                .delegate_record(delegate_record_key)
+                .metadata(metadata.key())
+                .collection_mint(collection.collection_mint.key())
+                .collection_metadata(Some(collection.collection_metadata.key()))
+                .collection_master_edition(Some(collection.collection_master_edition.key()))
+                .instruction()
+        }
+        None => {
+            let collection_metadata_obj = Metadata::try_from(&collection.collection_metadata)?;
+            match collection_metadata_obj.collection_details {
+                Some(_) => VerifySizedCollectionItemBuilder::new()
+                    .metadata(metadata.key())
+                    .collection_authority(collection.collection_authority.key())
+                    .payer(payer.key())
+                    .collection_mint(collection.collection_mint.key())
+                    .collection(collection.collection_metadata.key())
+                    .collection_master_edition_account(collection.collection_master_edition.key())
+                    .collection_authority_record(delegate_record_key)
+                    .instruction(),
+                None => VerifyCollectionBuilder::new()
+                    .metadata(metadata.key())
+                    .collection_authority(collection.collection_authority.key())
+                    .payer(payer.key())
+                    .collection_mint(collection.collection_mint.key())
+                    .collection(collection.collection_metadata.key())
+                    .collection_master_edition_account(collection.collection_master_edition.key())
+                    .collection_authority_record(delegate_record_key)
+                    .instruction(),
+            }
+        }
+    };
+
+    match authority_seeds {
+        Some(x) => {
+            invoke_signed(&ix, verify_infos.as_slice(), &[x])?;
+        }
+        None => {
+            invoke(&ix, verify_infos.as_slice())?;
+        }
+    }
+
+    Ok(())
+}